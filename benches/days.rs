@@ -0,0 +1,112 @@
+use aoc::erosion::Init;
+use aoc::{day01, day02, day03, day04, day05, day06, synthetic};
+use criterion::measurement::WallTime;
+use criterion::{BenchmarkGroup, BenchmarkId, Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+/// Every puzzle input, indexed by day - 1.
+const REAL: [&str; 6] = [
+    include_str!("../inputs/01.in"),
+    include_str!("../inputs/02.in"),
+    include_str!("../inputs/03.in"),
+    include_str!("../inputs/04.in"),
+    include_str!("../inputs/05.in"),
+    include_str!("../inputs/06.in"),
+];
+
+/// Synthetic inputs roughly 10 to 100 times the size of the real ones.
+const SCALES: [usize; 6] = [100_000, 10_000, 10_000, 1_000, 100_000, 100_000];
+
+/// The real input and a synthetic one from a fixed seed, by name.
+fn inputs(day: usize) -> [(&'static str, String); 2] {
+    let synthetic = synthetic::generate(day as u32, SCALES[day - 1], 0).unwrap();
+    [
+        ("real", REAL[day - 1].trim_end().to_string()),
+        ("synthetic", synthetic),
+    ]
+}
+
+/// Benchmarks parsing `input`, then both parts on the parsed value, named by `parts`.
+fn bench_day<T, A, B>(
+    group: &mut BenchmarkGroup<WallTime>,
+    (name, input): &(&str, String),
+    parse: impl Fn(&str) -> T,
+    parts: [&str; 2],
+    part_one: impl Fn(&T) -> A,
+    part_two: impl Fn(&T) -> B,
+) {
+    group.bench_with_input(BenchmarkId::new("parse", name), input, |b, input| {
+        b.iter(|| parse(black_box(input)))
+    });
+    let parsed = parse(input);
+    group.bench_with_input(BenchmarkId::new(parts[0], name), &parsed, |b, parsed| {
+        b.iter(|| part_one(black_box(parsed)))
+    });
+    group.bench_with_input(BenchmarkId::new(parts[1], name), &parsed, |b, parsed| {
+        b.iter(|| part_two(black_box(parsed)))
+    });
+}
+
+const PARTS: [&str; 2] = ["part-one", "part-two"];
+
+fn bench_days(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day01");
+    for input in &inputs(1) {
+        let parse = |input: &str| day01::parse_moves(input).unwrap();
+        let part_one = |moves: &Vec<_>| day01::part_one(moves);
+        let part_two = |moves: &Vec<_>| day01::part_two(moves);
+        bench_day(&mut group, input, parse, PARTS, part_one, part_two);
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("day02");
+    for input in &inputs(2) {
+        let parse = |input: &str| day02::parse_ranges::<u64>(input, 10).unwrap();
+        let part_one = |ranges: &Vec<_>| day02::part_one(ranges, 10);
+        let part_two = |ranges: &Vec<_>| day02::part_two(ranges, 10);
+        bench_day(&mut group, input, parse, PARTS, part_one, part_two);
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("day03");
+    for input in &inputs(3) {
+        let parse = |input: &str| day03::parse_banks(input).unwrap();
+        let part_one = |banks: &Vec<_>| day03::part_one(banks);
+        let part_two = |banks: &Vec<_>| day03::part_two(banks);
+        bench_day(&mut group, input, parse, PARTS, part_one, part_two);
+    }
+    group.finish();
+
+    // Both parts come out of the same erosion, so it is measured once per grid layout.
+    let mut group = c.benchmark_group("day04");
+    group.sample_size(20);
+    for input in &inputs(4) {
+        let parse = |input: &str| day04::parse_grid(input).unwrap();
+        let bytes = |grid: &_| day04::parts(grid, day04::Representation::Bytes, Init::Sequential);
+        let bits = |grid: &_| day04::parts(grid, day04::Representation::Bits, Init::Sequential);
+        let parts = ["parts-bytes", "parts-bits"];
+        bench_day(&mut group, input, parse, parts, bytes, bits);
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("day05");
+    for input in &inputs(5) {
+        let parse = |input: &str| day05::parse_input(input).unwrap();
+        let part_one = |(fresh, ids): &(_, Vec<_>)| day05::count_fresh(fresh, ids);
+        let part_two = |(fresh, _): &(aoc::interval::IntervalSet<u64>, _)| fresh.len();
+        bench_day(&mut group, input, parse, PARTS, part_one, part_two);
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("day06");
+    for input in &inputs(6) {
+        let parse = |input: &str| day06::parse_worksheet(input).unwrap();
+        let part_one = |worksheet: &day06::Worksheet| worksheet.part_one::<u64>();
+        let part_two = |worksheet: &day06::Worksheet| worksheet.part_two::<u64>();
+        bench_day(&mut group, input, parse, PARTS, part_one, part_two);
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_days);
+criterion_main!(benches);
@@ -0,0 +1,64 @@
+use aoc::bitgrid::BitGrid;
+use aoc::erosion::{Erosion, Init};
+use aoc::grid::{Grid, Neighborhood};
+use aoc::rng::SplitMix64;
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+const DAY_04: &str = include_str!("../inputs/04.in");
+
+/// A square grid of rolls where each cell is a roll with probability `density`,
+/// drawn from a fixed seed.
+fn generate(size: usize, density: f64) -> String {
+    let mut rng = SplitMix64::new(4);
+    let mut out = String::with_capacity(size * (size + 1));
+    for r in 0..size {
+        if r > 0 {
+            out.push('\n');
+        }
+        for _ in 0..size {
+            out.push(if rng.chance(density) { '@' } else { '.' });
+        }
+    }
+    out
+}
+
+fn is_roll(cell: &u8) -> bool {
+    *cell == b'@'
+}
+
+fn bench_grid(c: &mut Criterion) {
+    let inputs = [
+        ("real", DAY_04.trim_end().to_string()),
+        ("1000x1000", generate(1000, 0.7)),
+        ("4000x4000", generate(4000, 0.7)),
+    ];
+
+    let mut group = c.benchmark_group("erosion");
+    group.sample_size(20);
+    for (name, input) in &inputs {
+        let grid = Grid::from_ascii(input, b'.');
+        let erosion = Erosion::new(4, Neighborhood::Moore);
+        assert_eq!(
+            erosion.run(&grid, is_roll),
+            BitGrid::from_grid(&grid, is_roll).erode(4, Neighborhood::Moore)
+        );
+
+        group.bench_with_input(BenchmarkId::new("bytes", name), &grid, |b, grid| {
+            b.iter(|| erosion.run(black_box(grid), is_roll))
+        });
+        let parallel = erosion.clone().init(Init::Parallel);
+        group.bench_with_input(
+            BenchmarkId::new("bytes-parallel-init", name),
+            &grid,
+            |b, grid| b.iter(|| parallel.run(black_box(grid), is_roll)),
+        );
+        group.bench_with_input(BenchmarkId::new("bits", name), &grid, |b, grid| {
+            b.iter(|| BitGrid::from_grid(black_box(grid), is_roll).erode(4, Neighborhood::Moore))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_grid);
+criterion_main!(benches);
@@ -0,0 +1,54 @@
+use aoc::parse::{self, Cursor};
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+const DAY_02: &str = include_str!("../inputs/02.in");
+const DAY_05: &str = include_str!("../inputs/05.in");
+
+fn ranges_str(input: &str) -> Vec<(u64, u64)> {
+    input
+        .split(',')
+        .map(|range| {
+            let (start, end) = range.split_once('-').unwrap();
+            (start.parse().unwrap(), end.parse().unwrap())
+        })
+        .collect()
+}
+
+fn ranges_cursor(input: &str) -> Vec<(u64, u64)> {
+    Cursor::new(input)
+        .separated(b',', |c| c.pair(b'-'))
+        .unwrap()
+}
+
+fn numbers_str(input: &str) -> Vec<u64> {
+    input.lines().map(|line| line.parse().unwrap()).collect()
+}
+
+fn numbers_cursor(input: &str) -> Vec<u64> {
+    parse::lines(input, |c| c.int()).unwrap()
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let day_02 = DAY_02.trim_end();
+    let (_, day_05_numbers) = DAY_05.trim_end().split_once("\n\n").unwrap();
+    assert_eq!(ranges_str(day_02), ranges_cursor(day_02));
+    assert_eq!(numbers_str(day_05_numbers), numbers_cursor(day_05_numbers));
+
+    let mut group = c.benchmark_group("ranges");
+    group.bench_function("str::parse", |b| b.iter(|| ranges_str(black_box(day_02))));
+    group.bench_function("cursor", |b| b.iter(|| ranges_cursor(black_box(day_02))));
+    group.finish();
+
+    let mut group = c.benchmark_group("numbers");
+    group.bench_function("str::parse", |b| {
+        b.iter(|| numbers_str(black_box(day_05_numbers)))
+    });
+    group.bench_function("cursor", |b| {
+        b.iter(|| numbers_cursor(black_box(day_05_numbers)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse);
+criterion_main!(benches);
@@ -0,0 +1,74 @@
+use aoc::day05;
+use aoc::rng::SplitMix64;
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use std::fmt::Write;
+use std::hint::black_box;
+
+const DAY_05: &str = include_str!("../inputs/05.in");
+
+/// A day 5 input with `ranges` fresh ranges and `ids` ingredient IDs, scattered over
+/// a space wide enough that about half of the IDs are fresh.
+fn generate(ranges: usize, ids: usize) -> String {
+    let mut rng = SplitMix64::new(5);
+    let space = ranges as u64 * 1_000_000;
+    let mut out = String::new();
+    for _ in 0..ranges {
+        let start = rng.below(space);
+        let len = rng.below(1_400_000);
+        writeln!(out, "{start}-{}", start + len).unwrap();
+    }
+    for _ in 0..ids {
+        write!(out, "\n{}", rng.below(space)).unwrap();
+    }
+    out
+}
+
+/// The previous approach: every ID against every range.
+fn count_fresh_naive(ranges: &[(u64, u64)], ids: &[u64]) -> u64 {
+    ids.iter()
+        .filter(|&&id| ranges.iter().any(|&(start, end)| start <= id && id <= end))
+        .count() as u64
+}
+
+fn bench_ranges(c: &mut Criterion) {
+    let inputs = [
+        ("real", DAY_05.trim_end().to_string()),
+        ("10k", generate(10_000, 10_000)),
+        ("1M", generate(1_000_000, 1_000_000)),
+    ];
+
+    let mut group = c.benchmark_group("fresh");
+    group.sample_size(10);
+    for (name, input) in &inputs {
+        let (fresh, ids) = day05::parse_input(input).unwrap();
+        group.bench_with_input(BenchmarkId::new("binary-search", name), &ids, |b, ids| {
+            b.iter(|| day05::count_fresh(&fresh, black_box(ids)))
+        });
+        group.bench_with_input(BenchmarkId::new("solve", name), input, |b, input| {
+            b.iter(|| day05::solve(black_box(input)))
+        });
+
+        // Quadratic, so only on inputs where it finishes in reasonable time.
+        if ids.len() <= 10_000 {
+            let (ranges, _) = input.split_once("\n\n").unwrap();
+            let ranges: Vec<(u64, u64)> = ranges
+                .lines()
+                .map(|line| {
+                    let (start, end) = line.split_once('-').unwrap();
+                    (start.parse().unwrap(), end.parse().unwrap())
+                })
+                .collect();
+            assert_eq!(
+                count_fresh_naive(&ranges, &ids),
+                day05::count_fresh(&fresh, &ids)
+            );
+            group.bench_with_input(BenchmarkId::new("nested-loop", name), &ids, |b, ids| {
+                b.iter(|| count_fresh_naive(&ranges, black_box(ids)))
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_ranges);
+criterion_main!(benches);
@@ -0,0 +1,8 @@
+#![no_main]
+
+use aoc::day01;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = day01::solve(input);
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use aoc::day02;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = day02::solve(input);
+});
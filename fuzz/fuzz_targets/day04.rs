@@ -0,0 +1,15 @@
+#![no_main]
+
+use aoc::day04::{self, Representation};
+use aoc::erosion::Init;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    // Both grid representations and initializations must accept the same inputs and
+    // agree on them.
+    let bytes = day04::solve_with(input, Representation::Bytes, Init::Sequential);
+    let bits = day04::solve_with(input, Representation::Bits, Init::Sequential);
+    assert_eq!(bytes, bits);
+    let parallel = day04::solve_with(input, Representation::Bytes, Init::Parallel);
+    assert_eq!(bytes, parallel);
+});
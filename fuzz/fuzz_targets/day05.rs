@@ -0,0 +1,8 @@
+#![no_main]
+
+use aoc::day05;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = day05::solve(input);
+});
@@ -0,0 +1,13 @@
+#![no_main]
+
+use aoc::day06;
+use libfuzzer_sys::fuzz_target;
+use num_bigint::BigUint;
+
+fuzz_target!(|input: &str| {
+    // Whatever fits into u64 must agree with the arbitrary-precision result.
+    let big = day06::solve::<BigUint>(input);
+    if let Ok((p1, p2)) = day06::solve::<u64>(input) {
+        assert_eq!(big, Ok((p1.into(), p2.into())));
+    }
+});
@@ -0,0 +1,140 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Ident, ItemFn, LitInt, ReturnType, Token, Type, parse_macro_input};
+
+/// `#[aoc::main(DAY)]` or `#[aoc::main(DAY, args = handler)]`.
+/// The solution returns both answers, either directly or wrapped in a `Result` whose
+/// error implements `aoc::parse::Located`.
+struct MainArgs {
+    day: LitInt,
+    /// Called with the input and the command line arguments instead of solving,
+    /// whenever arguments other than `--input PATH` and `--threads N` are given.
+    args_handler: Option<Ident>,
+}
+
+impl Parse for MainArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let day = input.parse()?;
+        let mut args_handler = None;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key: Ident = input.parse()?;
+            if key != "args" {
+                return Err(syn::Error::new(key.span(), "expected `args = handler`"));
+            }
+            input.parse::<Token![=]>()?;
+            args_handler = Some(input.parse()?);
+        }
+        Ok(Self { day, args_handler })
+    }
+}
+
+/// Whether the solution returns a `Result` rather than the answers directly.
+fn returns_result(output: &ReturnType) -> bool {
+    let ReturnType::Type(_, ty) = output else {
+        return false;
+    };
+    let Type::Path(path) = ty.as_ref() else {
+        return false;
+    };
+    path.path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "Result")
+}
+
+#[proc_macro_attribute]
+pub fn main(args: TokenStream, input: TokenStream) -> TokenStream {
+    let MainArgs { day, args_handler } = parse_macro_input!(args as MainArgs);
+    let input_path = format!("../../inputs/{}.in", day);
+    let day_number: u32 = match day.base10_parse() {
+        Ok(day) => day,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let mut aoc_solution = parse_macro_input!(input as ItemFn);
+    aoc_solution.sig.ident = Ident::new("aoc_solution", aoc_solution.sig.ident.span());
+
+    // Errors are reported on stderr instead of the answers, with a failing exit code,
+    // along with the input line they point at.
+    let answers = if returns_result(&aoc_solution.sig.output) {
+        quote! {
+          match result {
+            Ok(answers) => answers,
+            Err(err) => {
+              eprintln!("error: {}", ::aoc::parse::InputError::new(#day_number, input, &err));
+              ::std::process::exit(1);
+            }
+          }
+        }
+    } else {
+        quote! { result }
+    };
+
+    let handle_args = args_handler.map(|handler| {
+        quote! {
+          if !args.is_empty() {
+            #handler(input, &args);
+            return;
+          }
+        }
+    });
+
+    let tokens = quote! {
+      const INPUT: &str = include_str!(#input_path);
+      #aoc_solution
+      fn main() {
+        // `--input PATH` replaces the bundled puzzle input, e.g. with a generated one.
+        let mut args: Vec<String> = ::std::env::args().skip(1).collect();
+        let file = match args.iter().position(|arg| arg == "--input") {
+          Some(index) if index + 1 < args.len() => {
+            let path = args.remove(index + 1);
+            args.remove(index);
+            match ::std::fs::read_to_string(&path) {
+              Ok(contents) => Some(contents),
+              Err(err) => {
+                eprintln!("error: {}: {}", path, err);
+                ::std::process::exit(2);
+              }
+            }
+          }
+          Some(_) => {
+            eprintln!("error: --input needs a path");
+            ::std::process::exit(2);
+          }
+          None => None,
+        };
+        let input = file.as_deref().unwrap_or(INPUT).trim_end();
+        // `--threads` or `AOC_THREADS` start the pool before timing, so a day isn't
+        // charged for spinning it up.
+        let startup = match ::aoc::pool::requested(&mut args).and_then(|threads| {
+          threads.map(::aoc::pool::init).transpose()
+        }) {
+          Ok(startup) => startup,
+          Err(err) => {
+            eprintln!("error: {}", err);
+            ::std::process::exit(2);
+          }
+        };
+        #handle_args
+        let now = ::std::time::Instant::now();
+        let result = aoc_solution(input);
+        let elapsed = now.elapsed();
+        let (p1, p2) = #answers;
+        println!("Part one: {}", p1);
+        println!("Part two: {}", p2);
+        let print_time = |label: &str, elapsed: ::std::time::Duration| {
+          if elapsed.as_millis() > 0 {
+            println!("{}: {}ms", label, elapsed.as_millis());
+          } else {
+            println!("{}: {}μs", label, elapsed.as_micros());
+          }
+        };
+        if let Some(startup) = startup {
+          print_time("Pool", startup);
+        }
+        print_time("Time", elapsed);
+      }
+    };
+    TokenStream::from(tokens)
+}
@@ -1,166 +1,168 @@
-#[aoc::main(04)]
-fn main(input: &str) -> (u32, u32) {
+use aoc::day04::{Representation, solve, solve_with, timeline};
+use aoc::erosion::{Init, Timeline};
+use aoc::parse::{InputError, ParseError};
+#[cfg(feature = "visualize")]
+use aoc::visualize::write_gif;
+use aoc::visualize::{Palette, Rgb, timeline_frames};
+use std::error::Error;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+#[aoc::main(04, args = cli)]
+fn main(input: &str) -> Result<(u32, u32), ParseError> {
     solve(input)
 }
 
-/// A flattened grid with padding to avoid boundary checks.
-/// The grid is padded with a 1-cell border of '.' (inactive) cells.
-struct Grid {
-    /// Flattened grid data (padding included).
-    cells: Vec<u8>,
-    /// Active neighbor counts for each cell.
-    neighbor_counts: Vec<u8>,
-    /// Width of the grid including padding.
-    stride: usize,
-    /// Original height of the input grid.
-    inner_rows: usize,
-    /// Original width of the input grid.
-    inner_cols: usize,
-    /// Pre-calculated relative offsets for the 8 neighbors.
-    neighbor_offsets: [isize; 8],
+const USAGE: &str = "usage: 04 [--grid bytes|bits] [--init sequential|parallel] [--timeline] [--visualize PATH] [--scale N] [--colors EMPTY,ROLL,REMOVING,REMOVED]";
+
+/// `--grid` solves with the given grid representation and `--init` with the given
+/// initialization of the neighbor counts.
+/// `--timeline` prints the generation in which every roll was removed.
+/// `--visualize out.gif` animates the removals; for `.png` and `.ppm` paths one numbered
+/// image per generation is written instead; GIF and PNG need the `visualize` feature.
+/// `--scale` sets the pixel size of a cell and `--colors` the cell colors as
+/// comma-separated `RRGGBB` hex values.
+fn cli(input: &str, args: &[String]) {
+    match run_cli(input, args) {
+        Ok(()) => {}
+        Err(err) if err.is::<InputError>() => {
+            eprintln!("error: {err}");
+            std::process::exit(1);
+        }
+        Err(err) => {
+            eprintln!("{err}\n{USAGE}");
+            std::process::exit(2);
+        }
+    }
 }
 
-impl Grid {
-    fn new(input: &str) -> Self {
-        let lines: Vec<&str> = input.lines().collect();
-        let inner_rows = lines.len();
-        let inner_cols = lines[0].len();
-        // Stride is the width including 1-cell padding on left and right
-        let stride = inner_cols + 2;
-        let total_size = (inner_rows + 2) * stride;
-
-        let mut cells = vec![b'.'; total_size];
-        let neighbor_counts = vec![0u8; total_size];
-
-        // Parse input into the center of the padded grid
-        for (r, line) in lines.iter().enumerate() {
-            let row_start = (r + 1) * stride + 1;
-            for (c, ch) in line.bytes().enumerate() {
-                cells[row_start + c] = ch;
-            }
+fn run_cli(input: &str, args: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut representation = None;
+    let mut init = None;
+    let mut print_timeline = false;
+    let mut output = None;
+    let mut scale = 4;
+    let mut palette = Palette::default();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("missing value for {arg}"))
+        };
+        match arg.as_str() {
+            "--grid" => representation = Some(value()?.parse()?),
+            "--init" => init = Some(value()?.parse()?),
+            "--timeline" => print_timeline = true,
+            "--visualize" => output = Some(PathBuf::from(value()?)),
+            "--scale" => match value()?.parse()? {
+                0 => return Err("--scale must be at least 1".into()),
+                pixels => scale = pixels,
+            },
+            "--colors" => palette = parse_palette(value()?)?,
+            _ => return Err(format!("unknown argument {arg}").into()),
         }
+    }
 
-        // Pre-calculate offsets for neighbors (top-left to bottom-right)
-        let stride_isize = stride as isize;
-        let neighbor_offsets = [
-            -stride_isize - 1,
-            -stride_isize,
-            -stride_isize + 1,
-            -1,
-            1,
-            stride_isize - 1,
-            stride_isize,
-            stride_isize + 1,
-        ];
-
-        Self {
-            cells,
-            neighbor_counts,
-            stride,
-            inner_rows,
-            inner_cols,
-            neighbor_offsets,
+    let solve_requested = representation.is_some() || init.is_some();
+    let representation = representation.unwrap_or(Representation::Bytes);
+    let init = init.unwrap_or(Init::Sequential);
+    if solve_requested {
+        let (p1, p2) = solve_with(input, representation, init)
+            .map_err(|err| InputError::new(4, input, &err))?;
+        println!("Part one: {p1}");
+        println!("Part two: {p2}");
+        if !print_timeline && output.is_none() {
+            return Ok(());
         }
     }
 
-    /// Returns true if the cell at the given index is active ('@').
-    #[inline(always)]
-    fn is_active(&self, index: usize) -> bool {
-        self.cells[index] == b'@'
+    let timeline = timeline(input, init).map_err(|err| InputError::new(4, input, &err))?;
+    if print_timeline {
+        println!("{}", timeline.render());
     }
-
-    /// Marks the cell at the given index as inactive ('.').
-    #[inline(always)]
-    fn deactivate(&mut self, index: usize) {
-        self.cells[index] = b'.';
+    match output {
+        Some(path) => visualize(&timeline, &path, &palette, scale),
+        None if print_timeline => Ok(()),
+        None => Err("nothing to do".into()),
     }
+}
 
-    /// Calculates initial neighbor counts for all active cells and returns
-    /// a queue of cells that should die in the first iteration (count < 4).
-    fn initialize_counts_and_queue(&mut self) -> Vec<usize> {
-        let mut queue = Vec::with_capacity(self.inner_rows * self.inner_cols / 4);
-
-        // Iterate only over the inner (valid) grid area
-        for r in 1..=self.inner_rows {
-            for c in 1..=self.inner_cols {
-                let index = r * self.stride + c;
-                if self.is_active(index) {
-                    let mut count = 0;
-                    for &offset in &self.neighbor_offsets {
-                        // Safe unchecked access because of padding
-                        let neighbor_index = (index as isize + offset) as usize;
-                        if self.is_active(neighbor_index) {
-                            count += 1;
-                        }
-                    }
-                    self.neighbor_counts[index] = count;
-                    if count < 4 {
-                        queue.push(index);
-                    }
-                }
+fn parse_palette(colors: &str) -> Result<Palette, Box<dyn Error>> {
+    let colors = colors
+        .split(',')
+        .map(|hex| {
+            let digits = hex.strip_prefix('#').unwrap_or(hex);
+            if digits.len() != 6 || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return Err(format!("color {hex:?} is not 6 hex digits").into());
             }
-        }
-        queue
-    }
+            let rgb = u32::from_str_radix(digits, 16)?;
+            Ok(rgb.to_be_bytes()[1..].try_into().unwrap())
+        })
+        .collect::<Result<Vec<Rgb>, Box<dyn Error>>>()?;
+    let [empty, active, removing, removed] = colors[..] else {
+        return Err("expected 4 colors".into());
+    };
+    Ok(Palette {
+        empty,
+        active,
+        removing,
+        removed,
+    })
 }
 
-fn solve(input: &str) -> (u32, u32) {
-    let mut grid = Grid::new(input);
-    let mut queue = grid.initialize_counts_and_queue();
-
-    let part_one = queue.len() as u32;
-    let mut part_two = part_one;
-
-    // Process the queue of dying cells
-    let mut head = 0;
-    while head < queue.len() {
-        let current_index = queue[head];
-        head += 1;
-
-        grid.deactivate(current_index);
-
-        // Notify neighbors of the death
-        for &offset in &grid.neighbor_offsets {
-            let neighbor_index = (current_index as isize + offset) as usize;
-
-            // If neighbor is active, decrement its count
-            if grid.is_active(neighbor_index) {
-                grid.neighbor_counts[neighbor_index] -= 1;
-
-                // If neighbor drops below threshold (exactly to 3), it dies next
-                if grid.neighbor_counts[neighbor_index] == 3 {
-                    queue.push(neighbor_index);
-                    part_two += 1;
-                }
-            }
+fn visualize(
+    timeline: &Timeline,
+    path: &Path,
+    palette: &Palette,
+    scale: usize,
+) -> Result<(), Box<dyn Error>> {
+    let frames = timeline_frames(timeline, palette, scale);
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default();
+    match extension {
+        #[cfg(feature = "visualize")]
+        "gif" => return Ok(write_gif(&frames, 25, BufWriter::new(File::create(path)?))?),
+        #[cfg(not(feature = "visualize"))]
+        "gif" | "png" => {
+            return Err(format!("writing .{extension} needs the `visualize` feature").into());
         }
+        _ => {}
     }
 
-    (part_one, part_two)
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("frame");
+    for (generation, frame) in frames.iter().enumerate() {
+        let file = path.with_file_name(format!("{stem}-{generation:03}.{extension}"));
+        let w = BufWriter::new(File::create(file)?);
+        match extension {
+            #[cfg(feature = "visualize")]
+            "png" => frame.write_png(w)?,
+            "ppm" => frame.write_ppm(w)?,
+            _ => return Err(format!("unsupported image format {extension:?}").into()),
+        }
+    }
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE_INPUT: &str = "..@@.@@@@.
-@@@.@.@.@@
-@@@@@.@.@@
-@.@@@@..@.
-@@.@@@@.@@
-.@@@@@@@.@
-.@.@.@.@@@
-@.@@@.@@@@
-.@@@@@@@@.
-@.@.@@@.@.";
-
-    #[test]
-    fn test_part_one() {
-        assert_eq!(solve(EXAMPLE_INPUT).0, 13);
-    }
-
     #[test]
-    fn test_part_two() {
-        assert_eq!(solve(EXAMPLE_INPUT).1, 43);
+    fn test_cli_arguments() {
+        let palette = parse_palette("#000000,ffffff,FF0000,102030").unwrap();
+        assert_eq!(palette.removed, [0x10, 0x20, 0x30]);
+        assert!(parse_palette("0000000,ffffff,ff0000,102030").is_err());
+        assert!(parse_palette("fff,ffffff,ff0000,102030").is_err());
+        assert!(parse_palette("+00000,ffffff,ff0000,102030").is_err());
+        assert!(parse_palette("000000,ffffff,ff0000").is_err());
+
+        let args = ["--timeline", "--scale", "0"].map(String::from);
+        assert!(run_cli("@.@", &args).is_err());
+        let args = ["--init", "eager"].map(String::from);
+        assert!(run_cli("@.@", &args).is_err());
     }
 }
@@ -1,135 +1,40 @@
-#[aoc::main(06)]
-fn main(input: &str) -> (u64, u64) {
-    let (part_one, part_two) = rayon::join(|| part_one(input), || part_two(input));
+use aoc::day06::{Value, WorksheetError, solve};
+use aoc::parse::InputError;
+use num_bigint::BigUint;
 
-    (part_one, part_two)
+#[aoc::main(06, args = cli)]
+fn main(input: &str) -> Result<(u64, u64), WorksheetError> {
+    // On overflow, rerun with `--precision 128` or `--precision big`.
+    solve::<u64>(input)
 }
 
-enum Instruction {
-    Add,
-    Multiply,
-}
-
-fn parse_instructions(input: &str) -> Vec<Instruction> {
-    let mut instructions = Vec::new();
-    for line in input.lines() {
-        if line.contains('+') || line.contains('*') {
-            for instruction in line.split_whitespace() {
-                instructions.push(if instruction == "+" {
-                    Instruction::Add
-                } else {
-                    Instruction::Multiply
-                });
-            }
-        }
-    }
-    instructions
-}
-
-fn parse_part_one_input(input: &str) -> Vec<Vec<u64>> {
-    let first_line = input.lines().find(|l| !l.trim().is_empty()).unwrap();
-    let num_columns = first_line.split_whitespace().count();
-    let mut grid = vec![Vec::new(); num_columns];
-
-    for line in input.lines() {
-        if line.contains('+') || line.contains('*') {
-            continue;
-        }
-        for (j, num) in line.split_whitespace().enumerate() {
-            grid[j].push(num.parse::<u64>().unwrap());
-        }
-    }
-    grid
-}
-
-fn parse_part_two_input(input: &str) -> Vec<Vec<u64>> {
-    let lines: Vec<&str> = input.lines().collect();
-    let number_lines: Vec<&str> = lines
-        .iter()
-        .filter(|l| !l.contains('+') && !l.contains('*'))
-        .cloned()
-        .collect();
-
-    if number_lines.is_empty() {
-        return Vec::new();
-    }
-
-    let width = number_lines[0].len();
-    let mut col_parse = Vec::new();
-    let mut current_group = Vec::new();
-
-    for i in 0..width {
-        let mut num_val = 0u64;
-        let mut has_digit = false;
+const USAGE: &str = "usage: 06 --precision 64|128|big";
 
-        for line in &number_lines {
-            if let Some(&b) = line.as_bytes().get(i)
-                && b.is_ascii_digit()
-            {
-                has_digit = true;
-                num_val = num_val * 10 + (b - b'0') as u64;
-            }
-        }
-
-        if has_digit {
-            current_group.push(num_val);
-        } else if !current_group.is_empty() {
-            col_parse.push(current_group);
-            current_group = Vec::new();
-        }
-    }
-    if !current_group.is_empty() {
-        col_parse.push(current_group);
+/// `--precision` evaluates the worksheet in `u64`, `u128` or arbitrary precision.
+fn cli(input: &str, args: &[String]) {
+    let result = match args {
+        [flag, precision] if flag == "--precision" => match precision.as_str() {
+            "64" => print_solution::<u64>(input),
+            "128" => print_solution::<u128>(input),
+            "big" => print_solution::<BigUint>(input),
+            _ => usage(&format!("unknown precision {precision:?}")),
+        },
+        _ => usage("unknown arguments"),
+    };
+    if let Err(err) = result {
+        eprintln!("error: {}", InputError::new(6, input, &err));
+        std::process::exit(1);
     }
-
-    col_parse
 }
 
-fn calculate_result(instructions: &[Instruction], grid: &[Vec<u64>]) -> u64 {
-    let mut res = 0;
-    for (instruction, values) in instructions.iter().zip(grid.iter()) {
-        let mut current_value = 0;
-        match instruction {
-            Instruction::Add => {
-                current_value += values.iter().sum::<u64>();
-            }
-            Instruction::Multiply => {
-                current_value += values.iter().product::<u64>();
-            }
-        }
-        res += current_value;
-    }
-    res
+fn usage(err: &str) -> ! {
+    eprintln!("{err}\n{USAGE}");
+    std::process::exit(2);
 }
 
-fn part_one(input: &str) -> u64 {
-    let instructions = parse_instructions(input);
-    let grid = parse_part_one_input(input);
-    calculate_result(&instructions, &grid)
-}
-
-fn part_two(input: &str) -> u64 {
-    let instructions = parse_instructions(input);
-    let grid = parse_part_two_input(input);
-    calculate_result(&instructions, &grid)
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const INPUT: &str = "123 328  51 64 
- 45 64  387 23 
-  6 98  215 314
-*   +   *   +  ";
-
-    #[test]
-    fn test_part_one() {
-        assert_eq!(part_one(INPUT), 4277556);
-    }
-
-    #[test]
-    fn test_part_two() {
-        assert_eq!(part_two(INPUT), 3263827);
-    }
+fn print_solution<V: Value>(input: &str) -> Result<(), WorksheetError> {
+    let (p1, p2) = solve::<V>(input)?;
+    println!("Part one: {p1}");
+    println!("Part two: {p2}");
+    Ok(())
 }
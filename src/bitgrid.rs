@@ -0,0 +1,240 @@
+use crate::erosion::Outcome;
+use crate::grid::{Grid, Neighborhood};
+
+/// A grid of flags packed 64 cells per word, with an empty row above and below.
+/// Column `c` of a row lives in bit `c % 64` of the row's word `c / 64`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BitGrid {
+    /// Packed rows, padding rows included.
+    words: Vec<u64>,
+    /// Number of words per row.
+    stride: usize,
+    inner_rows: usize,
+    inner_cols: usize,
+}
+
+/// Number of bits of the bit-sliced neighbor counters, enough for up to 15 neighbors.
+const COUNTER_BITS: usize = 4;
+
+impl BitGrid {
+    /// Packs the cells of `grid` for which `is_active` holds.
+    pub fn from_grid<T>(grid: &Grid<T>, is_active: impl Fn(&T) -> bool) -> Self {
+        let stride = grid.cols().div_ceil(64);
+        let mut words = vec![0; (grid.rows() + 2) * stride];
+        for (r, row) in grid.iter_rows().enumerate() {
+            let row_start = (r + 1) * stride;
+            for (c, cell) in row.iter().enumerate() {
+                if is_active(cell) {
+                    words[row_start + c / 64] |= 1 << (c % 64);
+                }
+            }
+        }
+        Self {
+            words,
+            stride,
+            inner_rows: grid.rows(),
+            inner_cols: grid.cols(),
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.inner_rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.inner_cols
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> bool {
+        self.words[(row + 1) * self.stride + col / 64] >> (col % 64) & 1 == 1
+    }
+
+    /// Number of set cells.
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// The words of padded row `r` (0 and `rows + 1` are the empty padding rows).
+    #[inline(always)]
+    fn padded_row(&self, r: usize) -> &[u64] {
+        &self.words[r * self.stride..(r + 1) * self.stride]
+    }
+
+    /// Computes, for word `i` of padded row `r`, which active cells have fewer than
+    /// `threshold` active neighbors. All cells of the word are counted at once
+    /// with bit-sliced adders.
+    #[inline(always)]
+    fn removable(&self, r: usize, i: usize, threshold: u8, neighborhood: Neighborhood) -> u64 {
+        let (up, row, down) = (
+            self.padded_row(r - 1),
+            self.padded_row(r),
+            self.padded_row(r + 1),
+        );
+        let mut counter = [0u64; COUNTER_BITS];
+        match neighborhood {
+            Neighborhood::VonNeumann => {
+                add(&mut counter, up[i]);
+                add(&mut counter, down[i]);
+                add(&mut counter, west(row, i));
+                add(&mut counter, east(row, i));
+            }
+            Neighborhood::Moore => {
+                for line in [up, down] {
+                    add(&mut counter, west(line, i));
+                    add(&mut counter, line[i]);
+                    add(&mut counter, east(line, i));
+                }
+                add(&mut counter, west(row, i));
+                add(&mut counter, east(row, i));
+            }
+        }
+        row[i] & less_than(&counter, threshold)
+    }
+
+    /// Synchronously removes every set cell with fewer than `threshold` set neighbors
+    /// until none is left. Only rows next to a removal are re-examined.
+    pub fn erode(&mut self, threshold: u8, neighborhood: Neighborhood) -> Outcome {
+        let mut removals = vec![0u64; self.words.len()];
+        // Padded rows that may contain removable cells.
+        let mut dirty = vec![true; self.inner_rows + 2];
+        let mut first_wave = None;
+        let mut removed = 0;
+
+        loop {
+            let mut wave = 0;
+            for r in 1..=self.inner_rows {
+                if !dirty[r] {
+                    continue;
+                }
+                for i in 0..self.stride {
+                    let mask = self.removable(r, i, threshold, neighborhood);
+                    removals[r * self.stride + i] = mask;
+                    wave += mask.count_ones() as usize;
+                }
+            }
+            first_wave.get_or_insert(wave);
+            if wave == 0 {
+                break;
+            }
+            removed += wave;
+
+            // Apply the whole generation at once and mark the rows around removals.
+            let mut next_dirty = vec![false; dirty.len()];
+            for r in 1..=self.inner_rows {
+                if !dirty[r] {
+                    continue;
+                }
+                let range = r * self.stride..(r + 1) * self.stride;
+                let mut any = 0;
+                for (word, &mask) in self.words[range.clone()].iter_mut().zip(&removals[range]) {
+                    *word &= !mask;
+                    any |= mask;
+                }
+                if any != 0 {
+                    next_dirty[r - 1..=r + 1].fill(true);
+                }
+            }
+            dirty = next_dirty;
+        }
+
+        Outcome {
+            first_wave: first_wave.unwrap_or(0),
+            removed,
+        }
+    }
+}
+
+/// Bit `c` set iff cell `c - 1` of the row is set.
+#[inline(always)]
+fn west(row: &[u64], i: usize) -> u64 {
+    let carry = if i > 0 { row[i - 1] >> 63 } else { 0 };
+    row[i] << 1 | carry
+}
+
+/// Bit `c` set iff cell `c + 1` of the row is set.
+#[inline(always)]
+fn east(row: &[u64], i: usize) -> u64 {
+    let carry = row.get(i + 1).map_or(0, |w| w << 63);
+    row[i] >> 1 | carry
+}
+
+/// Adds a 1-bit value per lane to the bit-sliced counter with a ripple of half adders.
+#[inline(always)]
+fn add(counter: &mut [u64; COUNTER_BITS], mut carry: u64) {
+    for bit in counter.iter_mut() {
+        let sum = *bit ^ carry;
+        carry &= *bit;
+        *bit = sum;
+    }
+}
+
+/// Lanes whose bit-sliced count is below `threshold`.
+#[inline(always)]
+fn less_than(counter: &[u64; COUNTER_BITS], threshold: u8) -> u64 {
+    if threshold >= 1 << COUNTER_BITS {
+        return !0;
+    }
+    // Compare from the most significant bit down: a lane is smaller at the first
+    // bit where the threshold has a 1 and the count a 0, with all higher bits equal.
+    let mut less = 0;
+    let mut equal = !0;
+    for (k, &bit) in counter.iter().enumerate().rev() {
+        if threshold >> k & 1 == 1 {
+            less |= equal & !bit;
+            equal &= bit;
+        } else {
+            equal &= !bit;
+        }
+    }
+    less
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::erosion::Erosion;
+
+    const EXAMPLE_INPUT: &str = "..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.";
+
+    #[test]
+    fn test_matches_byte_grid() {
+        let grid = Grid::from_ascii(EXAMPLE_INPUT, b'.');
+        for neighborhood in [Neighborhood::Moore, Neighborhood::VonNeumann] {
+            for threshold in 0..=9 {
+                let expected = Erosion::new(threshold, neighborhood).run(&grid, |&c| c == b'@');
+                let mut bits = BitGrid::from_grid(&grid, |&c| c == b'@');
+                assert_eq!(bits.erode(threshold, neighborhood), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_word_boundaries() {
+        // Rows spanning several words, with neighbors across the word boundaries.
+        let row = |offset: usize| {
+            let mut line = vec!['.'; 130];
+            for c in [62, 63, 64, 65, 127, 128].map(|c| c + offset) {
+                line[c] = '@';
+            }
+            line.into_iter().collect::<String>()
+        };
+        let input = [row(0), row(1), row(0)].join("\n");
+        let grid = Grid::from_ascii(&input, b'.');
+        let bits = BitGrid::from_grid(&grid, |&c| c == b'@');
+        assert!(bits.get(0, 64) && bits.get(1, 129) && !bits.get(1, 62));
+        assert_eq!(bits.count(), 18);
+
+        let expected = Erosion::new(3, Neighborhood::Moore).run(&grid, |&c| c == b'@');
+        assert_eq!(bits.clone().erode(3, Neighborhood::Moore), expected);
+        assert_ne!(expected.removed, 0);
+    }
+}
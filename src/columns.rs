@@ -0,0 +1,153 @@
+/// A block of text lines addressed by character column.
+/// Lines may be ragged; missing characters read as spaces.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ColumnBlock {
+    rows: Vec<Vec<char>>,
+    width: usize,
+}
+
+/// A maximal run of columns that are not blank in every row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ColumnGroup {
+    /// First character column of the group.
+    pub start: usize,
+    /// One past the last character column of the group.
+    pub end: usize,
+}
+
+impl ColumnGroup {
+    pub fn width(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn contains(&self, col: usize) -> bool {
+        (self.start..self.end).contains(&col)
+    }
+}
+
+impl ColumnBlock {
+    pub fn new<'a>(lines: impl IntoIterator<Item = &'a str>) -> Self {
+        let rows: Vec<Vec<char>> = lines.into_iter().map(|l| l.chars().collect()).collect();
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        Self { rows, width }
+    }
+
+    /// Builds a block from every line of `text`.
+    pub fn parse(text: &str) -> Self {
+        Self::new(text.lines())
+    }
+
+    /// Number of rows.
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Number of character columns of the longest row.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The character at `(row, col)`, or a space past the end of the row.
+    #[inline]
+    pub fn char_at(&self, row: usize, col: usize) -> char {
+        self.rows[row].get(col).copied().unwrap_or(' ')
+    }
+
+    /// Returns true if column `col` is whitespace in every row.
+    pub fn is_blank_column(&self, col: usize) -> bool {
+        (0..self.height()).all(|r| self.char_at(r, col).is_whitespace())
+    }
+
+    /// All groups of non-blank columns, left to right.
+    pub fn groups(&self) -> Vec<ColumnGroup> {
+        let mut groups = Vec::new();
+        let mut start = None;
+        for col in 0..self.width {
+            match (self.is_blank_column(col), start) {
+                (false, None) => start = Some(col),
+                (true, Some(s)) => {
+                    groups.push(ColumnGroup { start: s, end: col });
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(s) = start {
+            groups.push(ColumnGroup {
+                start: s,
+                end: self.width,
+            });
+        }
+        groups
+    }
+
+    /// The text of every row within `group`, top to bottom.
+    /// Rows keep their spaces, so alignment inside the group is preserved.
+    pub fn read_rows(&self, group: ColumnGroup) -> impl Iterator<Item = String> + '_ {
+        (0..self.height()).map(move |r| {
+            (group.start..group.end)
+                .map(|c| self.char_at(r, c))
+                .collect()
+        })
+    }
+
+    /// The text of every column within `group` read top to bottom, left to right.
+    pub fn read_columns(&self, group: ColumnGroup) -> impl Iterator<Item = String> + '_ {
+        (group.start..group.end)
+            .map(move |c| (0..self.height()).map(|r| self.char_at(r, c)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "123 328  51 64
+ 45 64  387 23
+  6 98  215 314";
+
+    #[test]
+    fn test_groups() {
+        let block = ColumnBlock::parse(EXAMPLE_INPUT);
+        let groups = block.groups();
+        let bounds: Vec<_> = groups.iter().map(|g| (g.start, g.end)).collect();
+        assert_eq!(bounds, [(0, 3), (4, 7), (8, 11), (12, 15)]);
+    }
+
+    #[test]
+    fn test_read_rows_and_columns() {
+        let block = ColumnBlock::parse(EXAMPLE_INPUT);
+        let group = block.groups()[0];
+        assert_eq!(
+            block.read_rows(group).collect::<Vec<_>>(),
+            ["123", " 45", "  6"]
+        );
+        assert_eq!(
+            block.read_columns(group).collect::<Vec<_>>(),
+            ["1  ", "24 ", "356"]
+        );
+    }
+
+    #[test]
+    fn test_ragged_and_non_ascii() {
+        // The second row is shorter and the first contains multi-byte characters.
+        let block = ColumnBlock::parse("ä1  ö\n2\n3   x   ");
+        assert_eq!(block.width(), 8);
+        let groups = block.groups();
+        assert_eq!(
+            groups,
+            [
+                ColumnGroup { start: 0, end: 2 },
+                ColumnGroup { start: 4, end: 5 }
+            ]
+        );
+        assert_eq!(
+            block.read_columns(groups[0]).collect::<Vec<_>>(),
+            ["ä23", "1  "]
+        );
+        assert_eq!(
+            block.read_rows(groups[1]).collect::<Vec<_>>(),
+            ["ö", " ", "x"]
+        );
+    }
+}
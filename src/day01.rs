@@ -0,0 +1,294 @@
+use crate::parse::{self, Cursor, ErrorKind, ParseError};
+
+enum Direction {
+    Left,
+    Right,
+}
+
+pub struct Move {
+    direction: Direction,
+    steps: i64,
+}
+
+/// A move such as `L68`. Steps are limited to `u32`, so no dial arithmetic can overflow.
+fn parse_move(cursor: &mut Cursor) -> Result<Move, ParseError> {
+    let direction = match cursor.peek() {
+        Some(b'L') => Direction::Left,
+        Some(b'R') => Direction::Right,
+        Some(_) => return Err(cursor.error(ErrorKind::UnknownToken)),
+        None => return Err(cursor.error(ErrorKind::UnexpectedEnd)),
+    };
+    cursor.next_byte()?;
+    let steps = cursor.int::<u32>()?.into();
+    Ok(Move { direction, steps })
+}
+
+pub fn parse_moves(input: &str) -> Result<Vec<Move>, ParseError> {
+    parse::lines(input, parse_move)
+}
+
+const DIAL_SIZE: i64 = 100;
+const DIAL_START: i64 = 50;
+
+/// A circular dial with positions `0..size`.
+pub struct Dial {
+    size: i64,
+    position: i64,
+}
+
+/// What happened during a single move of the dial.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Step {
+    /// Position after the move.
+    pub position: i64,
+    /// Whether the move ends at 0.
+    pub hit_zero: bool,
+    /// How many times the dial points at 0 during the move, including where it ends.
+    pub zero_passes: i64,
+}
+
+impl Dial {
+    /// The largest size for which no move can overflow the position.
+    pub const MAX_SIZE: i64 = i64::MAX - u32::MAX as i64;
+
+    /// A dial of `size` positions pointing at `start`, wrapped onto the dial.
+    /// Returns `None` unless `size` is in `1..=MAX_SIZE`.
+    pub fn new(size: i64, start: i64) -> Option<Self> {
+        (1..=Self::MAX_SIZE).contains(&size).then(|| Self {
+            size,
+            position: start.rem_euclid(size),
+        })
+    }
+
+    pub fn size(&self) -> i64 {
+        self.size
+    }
+
+    pub fn position(&self) -> i64 {
+        self.position
+    }
+
+    fn apply_move(&self, m: &Move) -> i64 {
+        match m.direction {
+            Direction::Left => self.position - m.steps,
+            Direction::Right => self.position + m.steps,
+        }
+    }
+
+    /// Counts the multiples of `size` crossed by the move, i.e. the clicks landing on 0.
+    fn count_wraps(&self, m: &Move) -> i64 {
+        let (position, size) = (self.position, self.size);
+        match m.direction {
+            Direction::Right => (position + m.steps).div_euclid(size) - position.div_euclid(size),
+            Direction::Left => {
+                (position - 1).div_euclid(size) - (position - m.steps - 1).div_euclid(size)
+            }
+        }
+    }
+
+    /// Applies a single move.
+    pub fn step(&mut self, m: &Move) -> Step {
+        let zero_passes = self.count_wraps(m);
+        self.position = self.apply_move(m).rem_euclid(self.size);
+        Step {
+            position: self.position,
+            hit_zero: self.position == 0,
+            zero_passes,
+        }
+    }
+
+    /// Applies all moves, yielding what happened after each one.
+    pub fn trace<'a>(
+        mut self,
+        moves: impl IntoIterator<Item = &'a Move>,
+    ) -> impl Iterator<Item = Step> {
+        moves.into_iter().map(move |m| self.step(m))
+    }
+}
+
+/// The dial of the puzzle.
+fn puzzle_dial() -> Dial {
+    Dial::new(DIAL_SIZE, DIAL_START).expect("the dial size is positive")
+}
+
+pub fn part_one(moves: &[Move]) -> i64 {
+    puzzle_dial()
+        .trace(moves)
+        .filter(|step| step.hit_zero)
+        .count() as i64
+}
+
+pub fn part_two(moves: &[Move]) -> i64 {
+    // check every time the dial passes 0, not only hits 0
+    puzzle_dial()
+        .trace(moves)
+        .map(|step| step.zero_passes)
+        .sum()
+}
+
+pub fn solve(input: &str) -> Result<(i64, i64), ParseError> {
+    let moves = parse_moves(input)?;
+    let (part_one, part_two) = rayon::join(|| part_one(&moves), || part_two(&moves));
+
+    Ok((part_one, part_two))
+}
+
+/// Clicks the dial one step at a time.
+#[cfg(test)]
+mod reference {
+    use super::*;
+
+    /// Turns the dial one click at a time, counting every click that lands on 0.
+    pub fn trace(size: i64, start: i64, moves: &[Move]) -> Vec<Step> {
+        let mut position = start.rem_euclid(size);
+        moves
+            .iter()
+            .map(|m| {
+                let click = match m.direction {
+                    Direction::Left => size - 1,
+                    Direction::Right => 1,
+                };
+                let mut zero_passes = 0;
+                for _ in 0..m.steps {
+                    position = (position + click) % size;
+                    if position == 0 {
+                        zero_passes += 1;
+                    }
+                }
+                Step {
+                    position,
+                    hit_zero: position == 0,
+                    zero_passes,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+    use proptest::prelude::*;
+    const EXAMPLE_INPUT: &str = "L68
+L30
+R48
+L5
+R60
+L55
+L1
+L99
+R14
+L82";
+
+    #[test]
+    fn test_part_one() {
+        assert_eq!(part_one(&parse_moves(EXAMPLE_INPUT).unwrap()), 3);
+    }
+
+    #[test]
+    fn test_part_two() {
+        assert_eq!(part_two(&parse_moves(EXAMPLE_INPUT).unwrap()), 6);
+    }
+
+    #[test]
+    fn test_trace() {
+        let steps: Vec<Step> = puzzle_dial()
+            .trace(&parse_moves(EXAMPLE_INPUT).unwrap())
+            .collect();
+        let positions: Vec<i64> = steps.iter().map(|s| s.position).collect();
+        assert_eq!(positions, [82, 52, 0, 95, 55, 0, 99, 0, 14, 32]);
+        let passes: Vec<i64> = steps.iter().map(|s| s.zero_passes).collect();
+        assert_eq!(passes, [1, 0, 1, 0, 1, 1, 0, 1, 0, 1]);
+    }
+
+    #[test]
+    fn test_other_dial_sizes() {
+        let moves = parse_moves("R7\nL3\nR25").unwrap();
+        let steps: Vec<Step> = Dial::new(10, 3).unwrap().trace(&moves).collect();
+        assert_eq!(
+            steps,
+            [
+                Step {
+                    position: 0,
+                    hit_zero: true,
+                    zero_passes: 1
+                },
+                Step {
+                    position: 7,
+                    hit_zero: false,
+                    zero_passes: 0
+                },
+                Step {
+                    position: 2,
+                    hit_zero: false,
+                    zero_passes: 3
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dial_size_must_be_positive() {
+        assert!(Dial::new(0, 5).is_none());
+        assert!(Dial::new(-10, 5).is_none());
+        assert!(Dial::new(Dial::MAX_SIZE + 1, 5).is_none());
+        let dial = Dial::new(10, -3).unwrap();
+        assert_eq!((dial.size(), dial.position()), (10, 7));
+    }
+
+    #[test]
+    fn test_matches_reference() {
+        let mut rng = SplitMix64::new(1);
+        for _ in 0..200 {
+            let size = 1 + rng.below(120) as i64;
+            let start = rng.below(size as u64) as i64;
+            let moves: Vec<String> = (0..rng.below(30))
+                .map(|_| {
+                    let direction = if rng.chance(0.5) { 'L' } else { 'R' };
+                    format!("{direction}{}", rng.below(3 * size as u64 + 2))
+                })
+                .collect();
+            let parsed = parse_moves(&moves.join("\n")).unwrap();
+            let expected = reference::trace(size, start, &parsed);
+            let steps: Vec<Step> = Dial::new(size, start).unwrap().trace(&parsed).collect();
+            assert_eq!(
+                steps, expected,
+                "size {size}, start {start}, moves {moves:?}"
+            );
+        }
+    }
+
+    /// Move lists such as `L68\nR5`, including moves of several full turns.
+    fn moves() -> impl Strategy<Value = String> {
+        prop::collection::vec((prop::bool::ANY, 0..350i32), 1..40).prop_map(|moves| {
+            moves
+                .into_iter()
+                .map(|(left, steps)| format!("{}{steps}", if left { 'L' } else { 'R' }))
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+    }
+
+    #[test]
+    fn test_malformed_moves() {
+        let error = |input| parse_moves(input).err().map(|err| err.to_string());
+        assert_eq!(error("R5\nX5"), Some("2:1: unknown token".to_string()));
+        assert_eq!(error("L"), Some("1:2: unexpected end of input".to_string()));
+        assert_eq!(error("R-5"), Some("1:2: expected a number".to_string()));
+        assert_eq!(
+            error("R4294967296"),
+            Some("1:2: number out of range".to_string())
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn prop_parts_match_reference(input in moves()) {
+            let moves = parse_moves(&input).unwrap();
+            let steps = reference::trace(DIAL_SIZE, DIAL_START, &moves);
+            prop_assert_eq!(part_one(&moves), steps.iter().filter(|s| s.hit_zero).count() as i64);
+            prop_assert_eq!(part_two(&moves), steps.iter().map(|s| s.zero_passes).sum::<i64>());
+        }
+    }
+}
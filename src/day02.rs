@@ -0,0 +1,514 @@
+use crate::digits::{Unsigned, checked_pattern_multiplier, checked_pow, digit_count};
+use crate::interval::Interval;
+use crate::parse::{Cursor, ErrorKind, Located, ParseError};
+use rayon::prelude::*;
+use std::fmt;
+
+/// The result does not fit into the chosen integer type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Overflow;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    Parse(ParseError),
+    Overflow,
+    /// Numbers can only be written in bases 2 to 36.
+    InvalidBase(u32),
+}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Self::Parse(err)
+    }
+}
+
+impl From<Overflow> for Error {
+    fn from(_: Overflow) -> Self {
+        Self::Overflow
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(err) => err.fmt(f),
+            Self::Overflow => write!(f, "sums overflow"),
+            Self::InvalidBase(base) => write!(f, "base {base} is not between 2 and 36"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Located for Error {
+    fn location(&self) -> Option<(usize, usize)> {
+        match self {
+            Self::Parse(err) => err.location(),
+            Self::Overflow | Self::InvalidBase(_) => None,
+        }
+    }
+}
+
+/// Which numbers made of a repeated digit pattern to select.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Repetition {
+    /// Some pattern repeated exactly `k` times, e.g. 1111 is "11" repeated twice.
+    Exactly(u32),
+    /// Some pattern repeated at least `k` times.
+    AtLeast(u32),
+    /// The shortest repeating pattern has exactly `p` digits.
+    PrimitivePeriod(u32),
+}
+
+/// How many numbers matched and what they add up to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Tally<T> {
+    pub count: T,
+    pub sum: T,
+}
+
+impl<T: Unsigned> Tally<T> {
+    const ZERO: Self = Tally {
+        count: T::ZERO,
+        sum: T::ZERO,
+    };
+
+    fn checked_add(self, other: Self) -> Result<Self, Overflow> {
+        Ok(Tally {
+            count: self.count.checked_add(other.count).ok_or(Overflow)?,
+            sum: self.sum.checked_add(other.sum).ok_or(Overflow)?,
+        })
+    }
+
+    /// Adds `other` with `sign` 1 or -1, modulo `T::MAX + 1`.
+    fn wrapping_add_signed(self, other: Self, sign: i32) -> Self {
+        let add = |a: T, b: T| {
+            if sign < 0 {
+                a.wrapping_sub(b)
+            } else {
+                a.wrapping_add(b)
+            }
+        };
+        Tally {
+            count: add(self.count, other.count),
+            sum: add(self.sum, other.sum),
+        }
+    }
+}
+
+/// Parses the digits of a number written in `base`.
+fn parse_number<T: Unsigned>(cursor: &mut Cursor, base: u32) -> Result<T, ParseError> {
+    let start = cursor.clone();
+    let digits = cursor.take_while(|b| char::from(b).is_digit(base));
+    if digits.is_empty() {
+        return Err(cursor.error(ErrorKind::ExpectedNumber));
+    }
+    // Nothing but digits was taken, so parsing can only overflow.
+    let digits = std::str::from_utf8(digits).unwrap();
+    T::from_str_radix(digits, base).map_err(|_| start.error(ErrorKind::Overflow))
+}
+
+/// Parses a range in the format of "328412-412772", written in `base`.
+fn parse_range<T: Unsigned>(cursor: &mut Cursor, base: u32) -> Result<Interval<T>, ParseError> {
+    let invalid = cursor.error(ErrorKind::InvalidRange);
+    let start = parse_number(cursor, base)?;
+    cursor.expect(b'-')?;
+    let end = parse_number(cursor, base)?;
+    Interval::inclusive(start, end).ok_or(invalid)
+}
+
+/// Rejects bases that digits cannot be written in.
+fn check_base(base: u32) -> Result<(), Error> {
+    if (2..=36).contains(&base) {
+        Ok(())
+    } else {
+        Err(Error::InvalidBase(base))
+    }
+}
+
+/// Parses comma-separated ranges, e.g. "11-22,95-115".
+pub fn parse_ranges<T: Unsigned>(input: &str, base: u32) -> Result<Vec<Interval<T>>, Error> {
+    check_base(base)?;
+    let mut cursor = Cursor::new(input);
+    let ranges = cursor.separated(b',', |cursor| parse_range(cursor, base))?;
+    cursor.finish()?;
+    Ok(ranges)
+}
+
+fn mobius(mut n: u32) -> i32 {
+    let mut result = 1;
+    let mut p = 2;
+    while p * p <= n {
+        if n.is_multiple_of(p) {
+            n /= p;
+            if n.is_multiple_of(p) {
+                return 0;
+            }
+            result = -result;
+        }
+        p += 1;
+    }
+    if n > 1 { -result } else { result }
+}
+
+fn divisors(n: u32) -> impl Iterator<Item = u32> {
+    (1..=n).filter(move |d| n.is_multiple_of(*d))
+}
+
+/// Numbers in the range with `l_total` digits whose digits repeat every `l_pat` digits,
+/// i.e. of the form P * multiplier for an `l_pat`-digit pattern P.
+fn periodic<T: Unsigned>(
+    range: &Interval<T>,
+    l_total: u32,
+    l_pat: u32,
+    base: u32,
+) -> Result<Tally<T>, Overflow> {
+    // Calculate multiplier: (base^l_total - 1) / (base^l_pat - 1)
+    // If it does not fit into T, neither does any number it produces.
+    let Some(multiplier) = checked_pattern_multiplier::<T>(l_total, l_pat, base) else {
+        return Ok(Tally::ZERO);
+    };
+
+    // Determine the range of pattern values P such that
+    // range.start <= P * multiplier <= range.end
+    // P >= ceil(range.start / multiplier)
+    // P <= floor(range.end() / multiplier)
+    let p_min_calc = range.start().div_ceil(multiplier);
+    let p_max_calc = range.end() / multiplier;
+
+    // P must be a valid l_pat-digit number (no leading zeros).
+    // base^(l_pat-1) <= P <= base^l_pat - 1
+    let p_min_digits: T = checked_pow(base, l_pat - 1).ok_or(Overflow)?;
+    let p_max_digits = checked_pow::<T>(base, l_pat).map_or(T::MAX, |p| p - T::ONE);
+
+    let p_start = p_min_calc.max(p_min_digits);
+    let p_end = p_max_calc.min(p_max_digits);
+
+    if p_start > p_end {
+        return Ok(Tally::ZERO);
+    }
+
+    // Arithmetic series p_start + ... + p_end, halving whichever factor is even.
+    // A single pattern is its own sum, even when p_start + p_end does not fit.
+    let count = p_end - p_start + T::ONE;
+    let two = T::ONE + T::ONE;
+    let pattern_sum = if count == T::ONE {
+        Some(p_start)
+    } else {
+        let ends = p_start.checked_add(p_end).ok_or(Overflow)?;
+        if count % two == T::ZERO {
+            (count / two).checked_mul(ends)
+        } else {
+            count.checked_mul(ends / two)
+        }
+    };
+    let sum = pattern_sum
+        .and_then(|s| s.checked_mul(multiplier))
+        .ok_or(Overflow)?;
+    Ok(Tally { count, sum })
+}
+
+/// Numbers in the range with `l_total` digits whose shortest repeating pattern has `l_pat` digits.
+/// Every number that repeats every d digits (d | l_pat) is counted by periodic(d);
+/// Möbius inversion over the divisors leaves the ones with l_pat as their shortest period.
+fn primitive<T: Unsigned>(
+    range: &Interval<T>,
+    l_total: u32,
+    l_pat: u32,
+    base: u32,
+) -> Result<Tally<T>, Overflow> {
+    // Partial sums may leave the range of T either way, so they wrap. The result is a
+    // subset of periodic(l_pat), whose tally fits, so the wrapped total is exact.
+    let mut tally = Tally::ZERO;
+    for d in divisors(l_pat) {
+        let sign = mobius(l_pat / d);
+        if sign != 0 {
+            tally = tally.wrapping_add_signed(periodic(range, l_total, d, base)?, sign);
+        }
+    }
+    Ok(tally)
+}
+
+/// Count and sum of the numbers in the range selected by `repetition`.
+fn tally_range<T: Unsigned>(
+    range: &Interval<T>,
+    base: u32,
+    repetition: Repetition,
+) -> Result<Tally<T>, Overflow> {
+    let mut tally = Tally::ZERO;
+    for l_total in digit_count(range.start(), base)..=digit_count(range.end(), base) {
+        let found = match repetition {
+            // Non-primitive patterns are fine here: 1111 is also "11" repeated twice.
+            Repetition::Exactly(k) if k > 0 && l_total.is_multiple_of(k) => {
+                periodic(range, l_total, l_total / k, base)?
+            }
+            Repetition::Exactly(_) => Tally::ZERO,
+            // Numbers with shortest pattern p repeat exactly l_total / p times at most.
+            Repetition::AtLeast(k) => divisors(l_total)
+                .filter(|&p| (l_total / p) >= k)
+                .try_fold(Tally::ZERO, |acc, p| {
+                    acc.checked_add(primitive(range, l_total, p, base)?)
+                })?,
+            Repetition::PrimitivePeriod(p) if p > 0 && l_total.is_multiple_of(p) => {
+                primitive(range, l_total, p, base)?
+            }
+            Repetition::PrimitivePeriod(_) => Tally::ZERO,
+        };
+        tally = tally.checked_add(found)?;
+    }
+    Ok(tally)
+}
+
+/// Count and sum of the numbers selected by `repetition` over all ranges.
+pub fn tally<T: Unsigned>(
+    ranges: &[Interval<T>],
+    base: u32,
+    repetition: Repetition,
+) -> Result<Tally<T>, Error> {
+    check_base(base)?;
+    Ok(ranges
+        .par_iter()
+        .map(|range| tally_range(range, base, repetition))
+        .try_reduce(|| Tally::ZERO, Tally::checked_add)?)
+}
+
+pub fn solve(input: &str) -> Result<(u64, u64), Error> {
+    solve_in::<u64>(input, 10)
+}
+
+/// Part 1: "made only of some sequence of digits repeated twice"
+pub fn part_one<T: Unsigned>(ranges: &[Interval<T>], base: u32) -> Result<T, Error> {
+    Ok(tally(ranges, base, Repetition::Exactly(2))?.sum)
+}
+
+/// Part 2: made of some sequence of digits repeated at least twice
+pub fn part_two<T: Unsigned>(ranges: &[Interval<T>], base: u32) -> Result<T, Error> {
+    Ok(tally(ranges, base, Repetition::AtLeast(2))?.sum)
+}
+
+/// Sums the numbers made of a repeated digit pattern in `base`, using `T` for all arithmetic.
+fn solve_in<T: Unsigned>(input: &str, base: u32) -> Result<(T, T), Error> {
+    let ranges: Vec<Interval<T>> = parse_ranges(input, base)?;
+    let (part_one, part_two) = rayon::join(|| part_one(&ranges, base), || part_two(&ranges, base));
+    Ok((part_one?, part_two?))
+}
+
+/// Tests every ID in each range by spelling out its digits.
+#[cfg(test)]
+mod reference {
+    use super::*;
+
+    /// Whether the digits repeat every `period` digits.
+    fn repeats_every(digits: &[u32], period: usize) -> bool {
+        digits.len().is_multiple_of(period)
+            && digits.iter().skip(period).zip(digits).all(|(a, b)| a == b)
+    }
+
+    fn matches(n: u64, base: u32, repetition: Repetition) -> bool {
+        let mut digits = Vec::new();
+        let mut rest = n;
+        loop {
+            digits.push((rest % base as u64) as u32);
+            rest /= base as u64;
+            if rest == 0 {
+                break;
+            }
+        }
+        let len = digits.len();
+        let shortest = (1..=len).find(|&p| repeats_every(&digits, p)).unwrap();
+        match repetition {
+            Repetition::Exactly(k) => {
+                k > 0 && len.is_multiple_of(k as usize) && repeats_every(&digits, len / k as usize)
+            }
+            // Repeating every `shortest` digits is the most repetitions possible.
+            Repetition::AtLeast(k) => len / shortest >= k as usize,
+            Repetition::PrimitivePeriod(p) => shortest == p as usize,
+        }
+    }
+
+    /// Tests every number of the range. IDs are positive, so 0 is never selected.
+    pub fn tally_range(range: &Interval<u64>, base: u32, repetition: Repetition) -> Tally<u64> {
+        let mut tally = Tally::ZERO;
+        for n in range.start().max(1)..=range.end() {
+            if matches(n, base, repetition) {
+                tally.count += 1;
+                tally.sum += n;
+            }
+        }
+        tally
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+    use proptest::prelude::*;
+
+    const EXAMPLE_INPUT: &str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
+
+    #[test]
+    fn test_part_one() {
+        assert_eq!(solve(EXAMPLE_INPUT).unwrap().0, 1227775554);
+    }
+
+    #[test]
+    fn test_part_two() {
+        assert_eq!(solve(EXAMPLE_INPUT).unwrap().1, 4174379265);
+    }
+
+    #[test]
+    fn test_other_bases() {
+        // 7 = 0b111 is a repeated pattern, 10 = 0b1010 is repeated twice.
+        assert_eq!(solve_in::<u32>("101-1010", 2), Ok((10, 17)));
+        assert_eq!(solve_in::<u64>("a-bb", 16), Ok((0x462, 0x462)));
+    }
+
+    #[test]
+    fn test_invalid_base() {
+        assert_eq!(
+            parse_ranges::<u64>("1-2", 40).err(),
+            Some(Error::InvalidBase(40))
+        );
+        let ranges = parse_ranges::<u64>("1-2", 10).unwrap();
+        assert_eq!(part_one(&ranges, 1), Err(Error::InvalidBase(1)));
+        assert_eq!(part_two(&ranges, 37), Err(Error::InvalidBase(37)));
+    }
+
+    #[test]
+    fn test_wide_integers() {
+        assert_eq!(
+            solve_in::<u128>("98765432109876543210-98765432109876543219", 10),
+            Ok((98765432109876543210, 98765432109876543210))
+        );
+        // Ranges ending just short of 20-digit pattern multipliers must not overflow.
+        assert_eq!(
+            solve_in::<u64>("18446744071844674407-18446744073709551615", 10),
+            Ok((18446744071844674407, 18446744071844674407))
+        );
+    }
+
+    #[test]
+    fn test_overflow_is_reported() {
+        let input =
+            "18446744071844674407-18446744071844674407,18446744071844674407-18446744071844674407";
+        assert_eq!(solve_in::<u64>(input, 10), Err(Error::Overflow));
+        assert!(solve_in::<u128>(input, 10).is_ok());
+    }
+
+    #[test]
+    fn test_no_false_overflow() {
+        let single = |n| Interval::inclusive(n, n).unwrap();
+        // A lone pattern near the maximum: p_start + p_end would not fit.
+        let max = single(u32::MAX);
+        let expected = Tally {
+            count: 1,
+            sum: u32::MAX,
+        };
+        assert_eq!(tally_range(&max, 10, Repetition::AtLeast(1)), Ok(expected));
+        assert_eq!(
+            tally_range(&max, 10, Repetition::PrimitivePeriod(10)),
+            Ok(expected)
+        );
+        // The periods 10 and 1 add up past u32::MAX, but the Möbius sum is zero.
+        let repdigit = single(3333333333u32);
+        assert_eq!(
+            tally_range(&repdigit, 10, Repetition::PrimitivePeriod(10)),
+            Ok(Tally::ZERO)
+        );
+    }
+
+    #[test]
+    fn test_repetition_queries() {
+        let ranges = parse_ranges::<u64>("1-1000000", 10).unwrap();
+        let query = |repetition| tally(&ranges, 10, repetition).unwrap();
+
+        // 111..999 and the 2-digit patterns repeated three times, 101010..999999.
+        assert_eq!(
+            query(Repetition::Exactly(3)),
+            Tally {
+                count: 99,
+                sum: 49550400
+            }
+        );
+        // Repdigits of every length, including the single digits.
+        assert_eq!(
+            query(Repetition::PrimitivePeriod(1)),
+            Tally {
+                count: 54,
+                sum: 5555520
+            }
+        );
+        // 2-digit patterns that are not repdigits, repeated once, twice or three times.
+        assert_eq!(query(Repetition::PrimitivePeriod(2)).count, 3 * 81);
+        assert_eq!(query(Repetition::AtLeast(1)).count, 1000000);
+        // Repdigits of 2 to 6 digits, 4- and 6-digit numbers with a primitive 2-digit
+        // pattern, and 6-digit numbers with a primitive 3-digit pattern.
+        assert_eq!(query(Repetition::AtLeast(2)).count, 5 * 9 + 2 * 81 + 891);
+    }
+
+    #[test]
+    fn test_matches_reference() {
+        let mut rng = SplitMix64::new(2);
+        for _ in 0..300 {
+            let base = 2 + rng.below(15) as u32;
+            let digits = 1 + rng.below(5) as u32;
+            let start = rng.below(base.pow(digits) as u64 * 3);
+            let range = Interval::inclusive(start, start + rng.below(2000)).unwrap();
+            let k = 1 + rng.below(4) as u32;
+            for repetition in [
+                Repetition::Exactly(k),
+                Repetition::AtLeast(k),
+                Repetition::PrimitivePeriod(k),
+            ] {
+                assert_eq!(
+                    tally_range(&range, base, repetition),
+                    Ok(reference::tally_range(&range, base, repetition)),
+                    "{}-{} in base {base}, {repetition:?}",
+                    range.start(),
+                    range.end()
+                );
+            }
+        }
+    }
+
+    /// Comma-separated ranges such as `11-22,95-115` of up to 8-digit IDs.
+    fn ranges() -> impl Strategy<Value = String> {
+        prop::collection::vec((1..100_000_000u64, 0..500u64), 1..6).prop_map(|ranges| {
+            ranges
+                .into_iter()
+                .map(|(start, len)| format!("{start}-{}", start + len))
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+    }
+
+    #[test]
+    fn test_malformed_ranges() {
+        let error = |input| solve(input).unwrap_err().to_string();
+        assert_eq!(error("11-22,95"), "1:9: unexpected end of input");
+        assert_eq!(error("11-22,9x-115"), "1:8: expected '-'");
+        assert_eq!(error("11-+22"), "1:4: expected a number");
+        assert_eq!(error("1-99999999999999999999"), "1:3: number out of range");
+        assert_eq!(error("11-22,\n95-115"), "1:7: expected a number");
+        assert_eq!(error("11-22;95-115"), "1:6: unexpected trailing input");
+        assert_eq!(error("11-22,115-95"), "1:7: range ends before it starts");
+    }
+
+    proptest! {
+        #[test]
+        fn prop_parts_match_reference(input in ranges()) {
+            let ranges = parse_ranges::<u64>(&input, 10).unwrap();
+            let expected = |repetition| {
+                ranges
+                    .iter()
+                    .map(|range| reference::tally_range(range, 10, repetition).sum)
+                    .sum::<u64>()
+            };
+            prop_assert_eq!(
+                solve(&input),
+                Ok((expected(Repetition::Exactly(2)), expected(Repetition::AtLeast(2))))
+            );
+        }
+    }
+}
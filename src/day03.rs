@@ -0,0 +1,118 @@
+use crate::parse::{self, ErrorKind, ParseError};
+use crate::select::select_max;
+use rayon::prelude::*;
+
+/// Every bank as its digits.
+pub fn parse_banks(input: &str) -> Result<Vec<Vec<u64>>, ParseError> {
+    parse::lines(input, |cursor| {
+        let mut digits = Vec::new();
+        while let Some(b) = cursor.peek() {
+            if !b.is_ascii_digit() {
+                return Err(cursor.error(ErrorKind::ExpectedNumber));
+            }
+            digits.push((b - b'0') as u64);
+            cursor.next_byte()?;
+        }
+        Ok(digits)
+    })
+}
+
+/// Largest number formed by picking `k` digits of the bank in order.
+fn max_joltage(digits: &[u64], k: usize) -> u64 {
+    select_max(digits, k)
+        .values
+        .into_iter()
+        .fold(0u64, |acc, digit| acc * 10 + digit)
+}
+
+/// The sum of the largest `k`-digit joltage of every bank, for measuring a part alone.
+fn total_joltage(banks: &[Vec<u64>], k: usize) -> u64 {
+    banks.par_iter().map(|digits| max_joltage(digits, k)).sum()
+}
+
+pub fn part_one(banks: &[Vec<u64>]) -> u64 {
+    total_joltage(banks, 2)
+}
+
+pub fn part_two(banks: &[Vec<u64>]) -> u64 {
+    total_joltage(banks, 12)
+}
+
+pub fn solve(input: &str) -> Result<(u64, u64), ParseError> {
+    // Pick the lexicographically largest subsequence of 2 (part one) or 12 (part two) digits.
+    // For example: 811111111111119 -> 8 and 9 => 89; 234234234234278 -> 7 and 8 => 78
+    let banks = parse_banks(input)?;
+    Ok(banks
+        .par_iter()
+        .map(|digits| rayon::join(|| max_joltage(digits, 2), || max_joltage(digits, 12)))
+        .reduce(|| (0, 0), |a, b| (a.0 + b.0, a.1 + b.1)))
+}
+
+/// Finds the largest joltage by dynamic programming over prefixes.
+#[cfg(test)]
+mod reference {
+    /// Dynamic programming over prefixes: `best[j]` is the largest number made of
+    /// `j` digits picked in order from the digits seen so far.
+    pub fn max_joltage(digits: &[u64], k: usize) -> u64 {
+        let k = k.min(digits.len());
+        let mut best: Vec<Option<u64>> = vec![None; k + 1];
+        best[0] = Some(0);
+        for &digit in digits {
+            for j in (1..=k).rev() {
+                if let Some(prefix) = best[j - 1] {
+                    let candidate = prefix * 10 + digit;
+                    best[j] = best[j].max(Some(candidate));
+                }
+            }
+        }
+        best[k].unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    const EXAMPLE_INPUT: &str = "987654321111111
+811111111111119
+234234234234278
+818181911112111";
+    #[test]
+    fn test_part_one() {
+        let (part_one, _part_two) = solve(EXAMPLE_INPUT).unwrap();
+        assert_eq!(part_one, 357);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let (_, part_two) = solve(EXAMPLE_INPUT).unwrap();
+        assert_eq!(part_two, 3121910778619);
+    }
+
+    #[test]
+    fn test_malformed_banks() {
+        let err = solve("12345\n12a45").unwrap_err();
+        assert_eq!(err.to_string(), "2:3: expected a number");
+    }
+
+    /// Banks of 12 to 100 digits from 1 to 9, one per line.
+    fn banks() -> impl Strategy<Value = String> {
+        let bank = prop::collection::vec(1..=9u8, 12..100)
+            .prop_map(|digits| digits.into_iter().map(|d| (b'0' + d) as char).collect());
+        prop::collection::vec(bank, 1..10).prop_map(|banks: Vec<String>| banks.join("\n"))
+    }
+
+    proptest! {
+        #[test]
+        fn prop_parts_match_reference(input in banks()) {
+            let expected = input.lines().fold((0, 0), |(one, two), line| {
+                let digits: Vec<u64> = line.bytes().map(|b| (b - b'0') as u64).collect();
+                (
+                    one + reference::max_joltage(&digits, 2),
+                    two + reference::max_joltage(&digits, 12),
+                )
+            });
+            prop_assert_eq!(solve(&input), Ok(expected));
+        }
+    }
+}
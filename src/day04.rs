@@ -0,0 +1,268 @@
+use crate::bitgrid::BitGrid;
+use crate::erosion::{Erosion, Init, Timeline};
+use crate::grid::{Grid, Neighborhood};
+use crate::parse::{ErrorKind, ParseError};
+
+/// How the grid of rolls is stored while removing them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Representation {
+    /// One byte per cell with a neighbor count per cell, see [`Erosion`].
+    Bytes,
+    /// 64 cells per word, counting neighbors of whole words at once, see [`BitGrid`].
+    Bits,
+}
+
+impl std::str::FromStr for Representation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bytes" => Ok(Self::Bytes),
+            "bits" => Ok(Self::Bits),
+            _ => Err(format!("unknown grid representation {s:?}")),
+        }
+    }
+}
+
+/// A roll is removed once fewer than 4 of its 8 neighbors are rolls.
+const THRESHOLD: u8 = 4;
+
+fn erosion(init: Init) -> Erosion {
+    Erosion::new(THRESHOLD, Neighborhood::Moore).init(init)
+}
+
+fn is_roll(cell: &u8) -> bool {
+    *cell == b'@'
+}
+
+/// The grid of rolls (`@`) and empty cells (`.`). Shorter lines are padded with empty cells.
+pub fn parse_grid(input: &str) -> Result<Grid<u8>, ParseError> {
+    for (r, line) in input.lines().enumerate() {
+        if let Some(c) = line.bytes().position(|b| b != b'@' && b != b'.') {
+            return Err(ParseError {
+                kind: ErrorKind::UnknownToken,
+                line: r + 1,
+                column: c + 1,
+            });
+        }
+    }
+    Ok(Grid::from_ascii(input, b'.'))
+}
+
+pub fn solve(input: &str) -> Result<(u32, u32), ParseError> {
+    solve_with(input, Representation::Bytes, Init::Sequential)
+}
+
+/// Both parts come out of a single erosion: part one is its first wave, part two
+/// everything it removes. `init` picks how [`Representation::Bytes`] counts the
+/// initial neighbors; the bit-packed grid always counts whole words.
+pub fn parts(grid: &Grid<u8>, representation: Representation, init: Init) -> (u32, u32) {
+    let outcome = match representation {
+        Representation::Bytes => erosion(init).run(grid, is_roll),
+        Representation::Bits => {
+            BitGrid::from_grid(grid, is_roll).erode(THRESHOLD, Neighborhood::Moore)
+        }
+    };
+    (outcome.first_wave as u32, outcome.removed as u32)
+}
+
+pub fn solve_with(
+    input: &str,
+    representation: Representation,
+    init: Init,
+) -> Result<(u32, u32), ParseError> {
+    Ok(parts(&parse_grid(input)?, representation, init))
+}
+
+pub fn timeline(input: &str, init: Init) -> Result<Timeline, ParseError> {
+    Ok(erosion(init).timeline(&parse_grid(input)?, is_roll))
+}
+
+/// Rescans the whole grid for removable rolls every generation.
+#[cfg(test)]
+mod reference {
+    /// Rescans the whole grid every generation and removes all accessible rolls at once.
+    /// Returns the generation in which every cell was removed, if it was.
+    pub fn generations(input: &str) -> Vec<Vec<Option<u32>>> {
+        let mut rolls: Vec<Vec<bool>> = input
+            .lines()
+            .map(|line| line.bytes().map(|b| b == b'@').collect())
+            .collect();
+        let mut generations: Vec<Vec<Option<u32>>> =
+            rolls.iter().map(|row| vec![None; row.len()]).collect();
+        let is_roll = |rolls: &[Vec<bool>], r: isize, c: isize| {
+            r >= 0
+                && c >= 0
+                && rolls.get(r as usize).and_then(|row| row.get(c as usize)) == Some(&true)
+        };
+
+        for generation in 1.. {
+            let mut removed = Vec::new();
+            for (r, row) in rolls.iter().enumerate() {
+                for (c, &roll) in row.iter().enumerate() {
+                    let (r, c) = (r as isize, c as isize);
+                    let neighbors = (-1..=1)
+                        .flat_map(|dr| (-1..=1).map(move |dc| (dr, dc)))
+                        .filter(|&(dr, dc)| (dr, dc) != (0, 0) && is_roll(&rolls, r + dr, c + dc))
+                        .count();
+                    if roll && neighbors < 4 {
+                        removed.push((r as usize, c as usize));
+                    }
+                }
+            }
+            if removed.is_empty() {
+                break;
+            }
+            for (r, c) in removed {
+                rolls[r][c] = false;
+                generations[r][c] = Some(generation);
+            }
+        }
+        generations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+    use proptest::prelude::*;
+
+    const EXAMPLE_INPUT: &str = "..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.";
+
+    #[test]
+    fn test_part_one() {
+        assert_eq!(solve(EXAMPLE_INPUT).unwrap().0, 13);
+    }
+
+    #[test]
+    fn test_part_two() {
+        assert_eq!(solve(EXAMPLE_INPUT).unwrap().1, 43);
+    }
+
+    #[test]
+    fn test_representations_agree() {
+        assert_eq!(
+            solve_with(EXAMPLE_INPUT, Representation::Bits, Init::Sequential),
+            Ok((13, 43))
+        );
+        assert_eq!("bits".parse(), Ok(Representation::Bits));
+        assert!("words".parse::<Representation>().is_err());
+    }
+
+    #[test]
+    fn test_init_strategies_agree() {
+        let input = crate::synthetic::generate(4, 300, 4).unwrap();
+        assert_eq!(
+            solve_with(&input, Representation::Bytes, Init::Parallel),
+            solve_with(&input, Representation::Bytes, Init::Sequential)
+        );
+        assert_eq!(
+            timeline(&input, Init::Parallel),
+            timeline(&input, Init::Sequential)
+        );
+        assert_eq!("parallel".parse(), Ok(Init::Parallel));
+        assert!("eager".parse::<Init>().is_err());
+    }
+
+    #[test]
+    fn test_malformed_grid() {
+        let err = solve("@.@\n.#.").unwrap_err();
+        assert_eq!(err.to_string(), "2:2: unknown token");
+        // Ragged rows are padded with empty cells.
+        assert_eq!(solve("@@\n@"), Ok((3, 3)));
+    }
+
+    #[test]
+    fn test_timeline() {
+        let timeline = timeline(EXAMPLE_INPUT, Init::Sequential).unwrap();
+        assert_eq!(timeline.generation_count(), 9);
+        assert_eq!(
+            timeline
+                .survivors
+                .render(|&alive| if alive { '@' } else { '.' }),
+            "..........
+..........
+..........
+....@@....
+...@@@@...
+...@@@@@..
+...@.@.@@.
+...@@.@@@.
+...@@@@@..
+....@@@..."
+        );
+    }
+
+    #[test]
+    fn test_matches_reference() {
+        let mut rng = SplitMix64::new(4);
+        for _ in 0..100 {
+            let (rows, cols) = (1 + rng.below(20) as usize, 1 + rng.below(20) as usize);
+            let density = rng.next_f64();
+            let input = (0..rows)
+                .map(|_| {
+                    (0..cols)
+                        .map(|_| if rng.chance(density) { '@' } else { '.' })
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            let expected = reference::generations(&input);
+            let first_wave = expected.iter().flatten().filter(|&&g| g == Some(1)).count() as u32;
+            let removed = expected.iter().flatten().flatten().count() as u32;
+            for representation in [Representation::Bytes, Representation::Bits] {
+                assert_eq!(
+                    solve_with(&input, representation, Init::Sequential),
+                    Ok((first_wave, removed)),
+                    "{input}"
+                );
+            }
+
+            let timeline = timeline(&input, Init::Sequential).unwrap();
+            let generations: Vec<Vec<Option<u32>>> = timeline
+                .generations
+                .iter_rows()
+                .map(|row| row.to_vec())
+                .collect();
+            assert_eq!(generations, expected, "{input}");
+        }
+    }
+
+    /// Rectangular grids of `@` and `.`.
+    fn grids() -> impl Strategy<Value = String> {
+        (1..25usize, 1..25usize).prop_flat_map(|(rows, cols)| {
+            let row = prop::collection::vec(prop::bool::weighted(0.65), cols).prop_map(|cells| {
+                cells
+                    .into_iter()
+                    .map(|roll| if roll { '@' } else { '.' })
+                    .collect()
+            });
+            prop::collection::vec(row, rows).prop_map(|rows: Vec<String>| rows.join("\n"))
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn prop_parts_match_reference(input in grids()) {
+            let generations = reference::generations(&input);
+            let cells = generations.iter().flatten();
+            let expected = (
+                cells.clone().filter(|&&g| g == Some(1)).count() as u32,
+                cells.flatten().count() as u32,
+            );
+            prop_assert_eq!(solve_with(&input, Representation::Bytes, Init::Sequential), Ok(expected));
+            prop_assert_eq!(solve_with(&input, Representation::Bits, Init::Sequential), Ok(expected));
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use crate::interval::{Interval, IntervalSet};
+use crate::parse::{self, Cursor, ErrorKind, Located, ParseError};
+use rayon::prelude::*;
+use std::fmt;
+
+/// Query lists at least this long are checked in parallel.
+const PARALLEL_QUERIES: usize = 1 << 14;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    Parse(ParseError),
+    /// The ranges cover more IDs than fit into `u64`.
+    Overflow,
+}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Self::Parse(err)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(err) => err.fmt(f),
+            Self::Overflow => write!(f, "the number of fresh IDs overflows"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Located for Error {
+    fn location(&self) -> Option<(usize, usize)> {
+        match self {
+            Self::Parse(err) => err.location(),
+            Self::Overflow => None,
+        }
+    }
+}
+
+/// The fresh ranges, merged into disjoint intervals, and the ingredient IDs to check.
+pub fn parse_input(input: &str) -> Result<(IntervalSet<u64>, Vec<u64>), ParseError> {
+    let Some((ranges, numbers)) = input.split_once("\n\n") else {
+        return Err(ParseError {
+            kind: ErrorKind::Expected(b'\n'),
+            line: input.lines().count().max(1),
+            column: input.lines().last().map_or(0, str::len) + 1,
+        });
+    };
+
+    let fresh = parse::lines(ranges, |cursor| {
+        let invalid = cursor.error(ErrorKind::InvalidRange);
+        let (start, end) = cursor.pair(b'-')?;
+        Interval::inclusive(start, end).ok_or(invalid)
+    })?
+    .into_iter()
+    .collect();
+
+    // The IDs start after the ranges and the blank line.
+    let first_line = ranges.lines().count() + 1;
+    let ids = numbers
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let mut cursor = Cursor::at_line(line, first_line + i);
+            let id = cursor.int()?;
+            cursor.finish()?;
+            Ok(id)
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok((fresh, ids))
+}
+
+/// Number of `ids` inside a fresh range, each found by binary search.
+pub fn count_fresh(fresh: &IntervalSet<u64>, ids: &[u64]) -> u64 {
+    if ids.len() >= PARALLEL_QUERIES {
+        ids.par_iter().filter(|&&id| fresh.contains(id)).count() as u64
+    } else {
+        ids.iter().filter(|&&id| fresh.contains(id)).count() as u64
+    }
+}
+
+/// Merges the ranges once; part two is the number of distinct fresh IDs.
+pub fn solve(input: &str) -> Result<(u64, u64), Error> {
+    let (fresh, ids) = parse_input(input)?;
+    let distinct = u64::try_from(fresh.len()).map_err(|_| Error::Overflow)?;
+    Ok((count_fresh(&fresh, &ids), distinct))
+}
+
+/// Checks every ID against every range and collects fresh IDs in a set.
+#[cfg(test)]
+mod reference {
+    use std::collections::HashSet;
+
+    /// Checks every ID against every range, and lists every fresh ID one by one.
+    pub fn solve(input: &str) -> (u64, u64) {
+        let (ranges, ids) = input.split_once("\n\n").unwrap();
+        let ranges: Vec<(u64, u64)> = ranges
+            .lines()
+            .map(|line| {
+                let (start, end) = line.split_once('-').unwrap();
+                (start.parse().unwrap(), end.parse().unwrap())
+            })
+            .collect();
+        let fresh = ids
+            .lines()
+            .map(|id| id.parse::<u64>().unwrap())
+            .filter(|&id| ranges.iter().any(|&(start, end)| start <= id && id <= end))
+            .count();
+        let distinct: HashSet<u64> = ranges
+            .iter()
+            .flat_map(|&(start, end)| start..=end)
+            .collect();
+        (fresh as u64, distinct.len() as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const INPUT: &str = "3-5
+10-14
+16-20
+12-18
+
+1
+5
+8
+11
+17
+32";
+
+    #[test]
+    fn test_part_one() {
+        assert_eq!(solve(INPUT).unwrap().0, 3);
+    }
+
+    #[test]
+    fn test_part_two() {
+        assert_eq!(solve(INPUT).unwrap().1, 14);
+    }
+
+    #[test]
+    fn test_parallel_queries() {
+        let (fresh, _) = parse_input(INPUT).unwrap();
+        let ids: Vec<u64> = (0..PARALLEL_QUERIES as u64 * 2).map(|id| id % 25).collect();
+        let expected = ids
+            .iter()
+            .filter(|&&id| (3..=5).contains(&id) || (10..=20).contains(&id));
+        assert_eq!(count_fresh(&fresh, &ids), expected.count() as u64);
+    }
+
+    #[test]
+    fn test_malformed_inventory() {
+        let error = |input| solve(input).unwrap_err().to_string();
+        assert_eq!(error("3-5\n10-14"), "2:6: expected '\\n'");
+        assert_eq!(error("3-5\n14-10\n\n1"), "2:1: range ends before it starts");
+        assert_eq!(error("3-5\n\n1\nx"), "4:1: expected a number");
+        // Every ID is fresh, one more than fits into the answer.
+        assert_eq!(solve("0-18446744073709551615\n\n5"), Err(Error::Overflow));
+        assert_eq!(solve("1-18446744073709551615\n\n5"), Ok((1, u64::MAX)));
+    }
+
+    /// Overlapping, nested and adjacent ranges followed by a blank line and IDs.
+    fn inventories() -> impl Strategy<Value = String> {
+        let ranges = prop::collection::vec((0..1000u64, 0..60u64), 1..20);
+        let ids = prop::collection::vec(0..1100u64, 1..50);
+        (ranges, ids).prop_map(|(ranges, ids)| {
+            let ranges: Vec<String> = ranges
+                .into_iter()
+                .map(|(start, len)| format!("{start}-{}", start + len))
+                .collect();
+            let ids: Vec<String> = ids.into_iter().map(|id| id.to_string()).collect();
+            format!("{}\n\n{}", ranges.join("\n"), ids.join("\n"))
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn prop_parts_match_reference(input in inventories()) {
+            prop_assert_eq!(solve(&input), Ok(reference::solve(&input)));
+        }
+    }
+}
@@ -0,0 +1,590 @@
+use crate::columns::{ColumnBlock, ColumnGroup};
+use crate::digits::{self, Unsigned};
+use crate::parse::{ErrorKind, Located, ParseError};
+use num_bigint::BigUint;
+use std::fmt::{self, Display};
+
+/// Non-negative numbers the worksheet can be evaluated in. All operations report
+/// overflow, and subtraction a result below zero, as `None`.
+pub trait Value: Clone + Ord + Display + Send + Sync + Sized {
+    fn zero() -> Self;
+    fn parse(digits: &str) -> Option<Self>;
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+    fn checked_sub(&self, other: &Self) -> Option<Self>;
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
+    /// Appends the decimal digits of `other`.
+    fn checked_concat(&self, other: &Self) -> Option<Self>;
+}
+
+impl<T: Unsigned> Value for T {
+    fn zero() -> Self {
+        T::ZERO
+    }
+
+    fn parse(digits: &str) -> Option<Self> {
+        T::from_str_radix(digits, 10).ok()
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Unsigned::checked_add(*self, *other)
+    }
+
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        Unsigned::checked_sub(*self, *other)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Unsigned::checked_mul(*self, *other)
+    }
+
+    fn checked_concat(&self, other: &Self) -> Option<Self> {
+        digits::checked_concat(*self, *other, 10)
+    }
+}
+
+impl Value for BigUint {
+    fn zero() -> Self {
+        BigUint::ZERO
+    }
+
+    fn parse(digits: &str) -> Option<Self> {
+        digits.parse().ok()
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        (self >= other).then(|| self - other)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Some(self * other)
+    }
+
+    fn checked_concat(&self, other: &Self) -> Option<Self> {
+        format!("{self}{other}").parse().ok()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Instruction {
+    /// `+`: the sum of the numbers.
+    Add,
+    /// `-`: the first number minus all others.
+    Subtract,
+    /// `*`: the product of the numbers.
+    Multiply,
+    /// `min`: the smallest number.
+    Min,
+    /// `max`: the largest number.
+    Max,
+    /// `||`: the decimal digits of the numbers written one after another.
+    Concat,
+}
+
+impl Instruction {
+    fn from_token(token: &str) -> Option<Self> {
+        Some(match token {
+            "+" => Self::Add,
+            "-" => Self::Subtract,
+            "*" => Self::Multiply,
+            "min" => Self::Min,
+            "max" => Self::Max,
+            "||" => Self::Concat,
+            _ => return None,
+        })
+    }
+
+    /// Combines `values` left to right, or `None` if that overflows or, for
+    /// [`Self::Subtract`], goes below zero. No values evaluate to zero.
+    fn apply<V: Value>(self, values: &[V]) -> Option<V> {
+        let Some((first, rest)) = values.split_first() else {
+            return Some(V::zero());
+        };
+        rest.iter()
+            .try_fold(first.clone(), |acc, value| match self {
+                Self::Add => acc.checked_add(value),
+                Self::Subtract => acc.checked_sub(value),
+                Self::Multiply => acc.checked_mul(value),
+                Self::Min => Some(acc.min(value.clone())),
+                Self::Max => Some(acc.max(value.clone())),
+                Self::Concat => acc.checked_concat(value),
+            })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WorksheetError {
+    Parse(ParseError),
+    /// No line holds operators.
+    MissingOperatorRow,
+    /// A second line holding operators, at the given 1-based line.
+    ExtraOperatorRow {
+        line: usize,
+    },
+    /// An operator that does not start under any column group, at a 1-based position.
+    Misaligned {
+        line: usize,
+        column: usize,
+    },
+    /// An operator under a column group that already has one, at a 1-based position.
+    SharedGroup {
+        line: usize,
+        column: usize,
+    },
+    /// The column group with the given 0-based index has no operator.
+    MissingOperator {
+        group: usize,
+    },
+    /// The numbers or the result of the problem in the given 0-based column group
+    /// (or the grand total, if `None`) do not fit the chosen precision.
+    Overflow {
+        group: Option<usize>,
+    },
+    /// A subtraction that goes below zero, at the 1-based position of its operator.
+    Negative {
+        line: usize,
+        column: usize,
+    },
+}
+
+impl Display for WorksheetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(err) => err.fmt(f),
+            Self::MissingOperatorRow => write!(f, "no operator row"),
+            Self::ExtraOperatorRow { line } => write!(f, "{line}: second operator row"),
+            Self::Misaligned { line, column } => {
+                write!(f, "{line}:{column}: operator is not under a column group")
+            }
+            Self::SharedGroup { line, column } => {
+                write!(f, "{line}:{column}: column group already has an operator")
+            }
+            Self::MissingOperator { group } => write!(f, "problem {} has no operator", group + 1),
+            Self::Overflow { group: Some(group) } => write!(f, "overflow in problem {}", group + 1),
+            Self::Overflow { group: None } => write!(f, "overflow in the grand total"),
+            Self::Negative { line, column } => {
+                write!(f, "{line}:{column}: subtraction goes below zero")
+            }
+        }
+    }
+}
+
+impl std::error::Error for WorksheetError {}
+
+impl Located for WorksheetError {
+    fn location(&self) -> Option<(usize, usize)> {
+        match *self {
+            Self::Parse(err) => err.location(),
+            Self::ExtraOperatorRow { line } => Some((line, 1)),
+            Self::Misaligned { line, column }
+            | Self::SharedGroup { line, column }
+            | Self::Negative { line, column } => Some((line, column)),
+            Self::MissingOperatorRow | Self::MissingOperator { .. } | Self::Overflow { .. } => None,
+        }
+    }
+}
+
+/// The whitespace-separated tokens of `line` with their 0-based character column.
+fn tokens(line: &str) -> Vec<(usize, &str)> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    // A trailing space closes the last token.
+    for (column, (byte, c)) in line.char_indices().chain([(line.len(), ' ')]).enumerate() {
+        match (c.is_whitespace(), start) {
+            (false, None) => start = Some((column, byte)),
+            (true, Some((column, start_byte))) => {
+                tokens.push((column, &line[start_byte..byte]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    tokens
+}
+
+/// The operator of a problem and its 1-based position in the input.
+#[derive(Clone, Copy, Debug)]
+struct Operator {
+    instruction: Instruction,
+    line: usize,
+    column: usize,
+}
+
+/// The operators of the 0-based `row` with their 0-based character column, if every token
+/// of it is an operator. Any other line is a number row and may only hold digits; a token
+/// that is neither a number nor an operator is reported before a misplaced operator.
+fn parse_operators(
+    row: usize,
+    line: &str,
+) -> Result<Option<Vec<(usize, Instruction)>>, WorksheetError> {
+    let tokens = tokens(line);
+    let operators: Option<Vec<_>> = tokens
+        .iter()
+        .map(|&(column, token)| Some((column, Instruction::from_token(token)?)))
+        .collect();
+    if let Some(operators) = operators.filter(|operators| !operators.is_empty()) {
+        return Ok(Some(operators));
+    }
+
+    let is_number = |token: &str| token.chars().all(|c| c.is_ascii_digit());
+    let unknown = tokens
+        .iter()
+        .find(|(_, token)| !is_number(token) && Instruction::from_token(token).is_none());
+    let bad = unknown.or_else(|| tokens.iter().find(|(_, token)| !is_number(token)));
+    match bad {
+        Some(&(column, token)) => Err(WorksheetError::Parse(ParseError {
+            kind: ErrorKind::UnknownToken,
+            line: row + 1,
+            column: column + token.chars().take_while(char::is_ascii_digit).count() + 1,
+        })),
+        None => Ok(None),
+    }
+}
+
+/// The number rows of a worksheet and the operator of every column group.
+pub struct Worksheet {
+    numbers: ColumnBlock,
+    problems: Vec<(ColumnGroup, Operator)>,
+}
+
+/// Finds the single operator row, wherever it is, and assigns each operator to the
+/// column group of the number rows its first character sits under.
+pub fn parse_worksheet(input: &str) -> Result<Worksheet, WorksheetError> {
+    let mut operator_row = None;
+    let mut number_rows = Vec::new();
+    for (row, line) in input.lines().enumerate() {
+        match parse_operators(row, line)? {
+            None => number_rows.push(line),
+            Some(operators) => {
+                if operator_row.replace((row, operators)).is_some() {
+                    return Err(WorksheetError::ExtraOperatorRow { line: row + 1 });
+                }
+            }
+        }
+    }
+    let (row, row_operators) = operator_row.ok_or(WorksheetError::MissingOperatorRow)?;
+
+    let numbers = ColumnBlock::new(number_rows);
+    let groups = numbers.groups();
+    let mut operators = vec![None; groups.len()];
+    for (start, instruction) in row_operators {
+        let (line, column) = (row + 1, start + 1);
+        // Groups are sorted, so each operator finds its group in O(log n) time.
+        let group = Some(groups.partition_point(|group| group.end <= start))
+            .filter(|&group| groups.get(group).is_some_and(|g| g.contains(start)))
+            .ok_or(WorksheetError::Misaligned { line, column })?;
+        let operator = Operator {
+            instruction,
+            line,
+            column,
+        };
+        if operators[group].replace(operator).is_some() {
+            return Err(WorksheetError::SharedGroup { line, column });
+        }
+    }
+
+    let problems = groups
+        .into_iter()
+        .zip(operators)
+        .enumerate()
+        .map(|(group, (columns, operator))| {
+            operator
+                .map(|operator| (columns, operator))
+                .ok_or(WorksheetError::MissingOperator { group })
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Worksheet { numbers, problems })
+}
+
+fn parse_numbers<V: Value>(texts: impl Iterator<Item = String>) -> Option<Vec<V>> {
+    texts
+        .filter_map(|text| {
+            let digits = text.trim();
+            (!digits.is_empty()).then(|| V::parse(digits))
+        })
+        .collect()
+}
+
+impl Worksheet {
+    /// Sums the results of all problems, reading the numbers of each column group with `read`.
+    fn evaluate<V: Value, I: Iterator<Item = String>>(
+        &self,
+        read: impl Fn(ColumnGroup) -> I,
+    ) -> Result<V, WorksheetError> {
+        let mut res = V::zero();
+        for (group, &(columns, operator)) in self.problems.iter().enumerate() {
+            let overflow = WorksheetError::Overflow { group: Some(group) };
+            let values = parse_numbers(read(columns)).ok_or(overflow.clone())?;
+            let failure = match operator.instruction {
+                // Subtracting only makes values smaller, so it fails just below zero.
+                Instruction::Subtract => WorksheetError::Negative {
+                    line: operator.line,
+                    column: operator.column,
+                },
+                _ => overflow,
+            };
+            let current_value = operator.instruction.apply(&values).ok_or(failure)?;
+            res = res
+                .checked_add(&current_value)
+                .ok_or(WorksheetError::Overflow { group: None })?;
+        }
+        Ok(res)
+    }
+
+    pub fn part_one<V: Value>(&self) -> Result<V, WorksheetError> {
+        // Numbers are read row by row within each column group.
+        self.evaluate(|group| self.numbers.read_rows(group))
+    }
+
+    pub fn part_two<V: Value>(&self) -> Result<V, WorksheetError> {
+        // Numbers are read top to bottom, one per character column. Shorter numbers in
+        // a left-aligned group leave gaps inside a column, which are skipped.
+        self.evaluate(|group| {
+            self.numbers
+                .read_columns(group)
+                .map(|column| column.split_whitespace().collect())
+        })
+    }
+}
+
+pub fn solve<V: Value>(input: &str) -> Result<(V, V), WorksheetError> {
+    let worksheet = parse_worksheet(input)?;
+    let (part_one, part_two) = rayon::join(|| worksheet.part_one(), || worksheet.part_two());
+    Ok((part_one?, part_two?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::InputError;
+    use proptest::prelude::*;
+
+    fn part_one<V: Value>(input: &str) -> Result<V, WorksheetError> {
+        parse_worksheet(input)?.part_one()
+    }
+
+    fn part_two<V: Value>(input: &str) -> Result<V, WorksheetError> {
+        parse_worksheet(input)?.part_two()
+    }
+
+    const INPUT: &str = "123 328  51 64 
+ 45 64  387 23 
+  6 98  215 314
+*   +   *   +  ";
+
+    #[test]
+    fn test_part_one() {
+        assert_eq!(part_one::<u64>(INPUT), Ok(4277556));
+    }
+
+    #[test]
+    fn test_part_two() {
+        assert_eq!(part_two::<u64>(INPUT), Ok(3263827));
+    }
+
+    #[test]
+    fn test_operators() {
+        let input = "10   7   3  12
+ 4   9  40   3
+-    min max ||";
+        // 10 - 4, min(7, 9), max(3, 40), 12 || 3
+        assert_eq!(part_one::<u64>(input), Ok(6 + 7 + 40 + 123));
+
+        let err = part_one::<u64>("1 2\n+ %").unwrap_err();
+        assert_eq!(err.to_string(), "2:3: unknown token");
+    }
+
+    #[test]
+    fn test_typos_in_number_rows() {
+        let error = |input| part_one::<u64>(input).unwrap_err().to_string();
+        assert_eq!(error("12 3\n1x 4\n+  *"), "2:2: unknown token");
+        assert_eq!(error("12 +\n+  *"), "1:4: unknown token");
+        assert_eq!(error("12 3\n+  *\n-  ||"), "3: second operator row");
+    }
+
+    #[test]
+    fn test_unsigned() {
+        let max = "18446744073709551615\n0\n+";
+        assert_eq!(part_one::<u64>(max), Ok(u64::MAX));
+        // 23 - 10 and 5 - 3 by rows, but 21 - 30 by columns.
+        let input = "23 5\n10 3\n-  -";
+        assert_eq!(part_one::<u64>(input), Ok(15));
+        assert_eq!(
+            part_two::<u64>(input),
+            Err(WorksheetError::Negative { line: 3, column: 1 })
+        );
+        assert_eq!(
+            part_one::<BigUint>("1 3\n1 5\n+ -"),
+            Err(WorksheetError::Negative { line: 3, column: 3 })
+        );
+        assert_eq!(part_one::<u64>("0 12\n0 3\n- ||"), Ok(123));
+    }
+
+    #[test]
+    fn test_operator_row_anywhere() {
+        let rows: Vec<&str> = INPUT.lines().collect();
+        let operators_first = [rows[3], rows[0], rows[1], rows[2]].join("\n");
+        assert_eq!(part_one::<u64>(&operators_first), Ok(4277556));
+        let operators_between = [rows[0], rows[3], rows[1], rows[2]].join("\n");
+        assert_eq!(part_two::<u64>(&operators_between), Ok(3263827));
+    }
+
+    #[test]
+    fn test_operator_alignment() {
+        let error = |input| part_one::<u64>(input).unwrap_err();
+        assert_eq!(
+            error("12 3\n+"),
+            WorksheetError::MissingOperator { group: 1 }
+        );
+        assert_eq!(
+            error("12 3\n+  * -"),
+            WorksheetError::Misaligned { line: 2, column: 6 }
+        );
+        assert_eq!(
+            error("123 4\n+ * -"),
+            WorksheetError::SharedGroup { line: 2, column: 3 }
+        );
+        assert_eq!(
+            error("12 3\n+  *\n*  +"),
+            WorksheetError::ExtraOperatorRow { line: 3 }
+        );
+        assert_eq!(error("12 3"), WorksheetError::MissingOperatorRow);
+    }
+
+    #[test]
+    fn test_error_snippet() {
+        let input = "12 3\n+  * -";
+        let err = part_one::<u64>(input).unwrap_err();
+        assert_eq!(
+            InputError::new(6, input, &err).to_string(),
+            "day 06: 2:6: operator is not under a column group\n  |\n2 | +  * -\n  |      ^"
+        );
+        let err = WorksheetError::Overflow { group: None };
+        assert_eq!(
+            InputError::new(6, input, &err).to_string(),
+            "day 06: overflow in the grand total"
+        );
+    }
+
+    #[test]
+    fn test_overflow_and_precision() {
+        let input = "4294967296 99
+4294967296 99
+*          ||";
+        assert_eq!(
+            part_one::<u64>(input),
+            Err(WorksheetError::Overflow { group: Some(0) })
+        );
+        assert_eq!(part_one::<u128>(input), Ok((1u128 << 64) + 9999));
+
+        let huge = "99999999999999999999999999999999999999999\n1\n+";
+        assert!(part_one::<u128>(huge).is_err());
+        assert_eq!(
+            part_one::<BigUint>(huge).unwrap().to_string(),
+            "100000000000000000000000000000000000000000"
+        );
+    }
+
+    /// One problem of a generated worksheet.
+    #[derive(Clone, Debug)]
+    struct Problem {
+        operator: &'static str,
+        numbers: Vec<i64>,
+        /// Whether the numbers are aligned to the right edge of the column group.
+        right_aligned: bool,
+    }
+
+    impl Problem {
+        fn width(&self) -> usize {
+            self.numbers
+                .iter()
+                .map(|n| n.to_string().len())
+                .max()
+                .unwrap()
+        }
+
+        fn padded(&self, row: usize) -> String {
+            let width = self.width();
+            match self.right_aligned {
+                true => format!("{:>width$}", self.numbers[row]),
+                false => format!("{:<width$}", self.numbers[row]),
+            }
+        }
+
+        fn apply(&self, values: impl Iterator<Item = i64>) -> i64 {
+            values
+                .reduce(|acc, value| match self.operator {
+                    "+" => acc + value,
+                    "-" => acc - value,
+                    _ => acc * value,
+                })
+                .unwrap()
+        }
+
+        /// The numbers read top to bottom, one per character column.
+        fn columns(&self) -> impl Iterator<Item = i64> + '_ {
+            let rows: Vec<String> = (0..self.numbers.len()).map(|r| self.padded(r)).collect();
+            (0..self.width()).map(move |c| {
+                let digits: String = rows.iter().map(|row| row.as_bytes()[c] as char).collect();
+                digits.trim().replace(' ', "").parse().unwrap()
+            })
+        }
+    }
+
+    /// Worksheets with the operator row at a random position, operators under the first
+    /// column of their group and groups separated by a single blank column.
+    fn worksheets() -> impl Strategy<Value = (String, Vec<Problem>)> {
+        (2..5usize).prop_flat_map(|height| {
+            let problem = (
+                prop::sample::select(vec!["+", "-", "*"]),
+                prop::collection::vec(1..10_000i64, height),
+                prop::bool::ANY,
+            )
+                .prop_map(|(operator, numbers, right_aligned)| Problem {
+                    operator,
+                    numbers,
+                    right_aligned,
+                });
+            (prop::collection::vec(problem, 1..12), 0..=height).prop_map(
+                move |(problems, operator_row)| {
+                    let mut lines: Vec<String> = (0..height)
+                        .map(|r| {
+                            let cells: Vec<String> = problems.iter().map(|p| p.padded(r)).collect();
+                            cells.join(" ")
+                        })
+                        .collect();
+                    let operators: Vec<String> = problems
+                        .iter()
+                        .map(|p| format!("{:<width$}", p.operator, width = p.width()))
+                        .collect();
+                    lines.insert(operator_row, operators.join(" "));
+                    (lines.join("\n"), problems)
+                },
+            )
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn prop_worksheets_evaluate((input, problems) in worksheets()) {
+            let total = |results: Vec<i64>| results.into_iter().map(u64::try_from).sum();
+            let by_rows = problems.iter().map(|p| p.apply(p.numbers.iter().copied()));
+            let by_columns = problems.iter().map(|p| p.apply(p.columns()));
+            match (total(by_rows.collect()), total(by_columns.collect())) {
+                (Ok(one), Ok(two)) => {
+                    prop_assert_eq!(solve::<u64>(&input), Ok((one, two)));
+                    prop_assert_eq!(solve::<BigUint>(&input), Ok((one.into(), two.into())));
+                }
+                // Some subtraction goes below zero.
+                _ => {
+                    let result = solve::<u64>(&input);
+                    let negative = matches!(result, Err(WorksheetError::Negative { .. }));
+                    prop_assert!(negative, "{:?}", result);
+                }
+            }
+        }
+    }
+}
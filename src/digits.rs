@@ -0,0 +1,265 @@
+use crate::interval::Discrete;
+use std::fmt;
+use std::hash::Hash;
+use std::iter::Sum;
+use std::num::ParseIntError;
+use std::ops::{Add, Div, Mul, Rem, Sub};
+
+/// Unsigned integer types the digit helpers work on.
+pub trait Unsigned:
+    Discrete
+    + Copy
+    + Ord
+    + fmt::Debug
+    + fmt::Display
+    + Hash
+    + Send
+    + Sync
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + Sum
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const MAX: Self;
+
+    fn from_u32(value: u32) -> Self;
+    /// Truncates to the low 32 bits.
+    fn as_u32(self) -> u32;
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseIntError>;
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn wrapping_add(self, rhs: Self) -> Self;
+    fn wrapping_sub(self, rhs: Self) -> Self;
+    fn checked_pow(self, exp: u32) -> Option<Self>;
+    fn pow(self, exp: u32) -> Self;
+    fn div_ceil(self, rhs: Self) -> Self;
+    fn ilog(self, base: Self) -> u32;
+}
+
+macro_rules! impl_unsigned {
+    ($($t:ty),*) => {$(
+        impl Unsigned for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            const MAX: Self = <$t>::MAX;
+
+            #[inline(always)]
+            fn from_u32(value: u32) -> Self {
+                value as $t
+            }
+
+            #[inline(always)]
+            fn as_u32(self) -> u32 {
+                self as u32
+            }
+
+            fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseIntError> {
+                <$t>::from_str_radix(s, radix)
+            }
+
+            #[inline(always)]
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+
+            #[inline(always)]
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_sub(self, rhs)
+            }
+
+            #[inline(always)]
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+
+            #[inline(always)]
+            fn wrapping_add(self, rhs: Self) -> Self {
+                <$t>::wrapping_add(self, rhs)
+            }
+
+            #[inline(always)]
+            fn wrapping_sub(self, rhs: Self) -> Self {
+                <$t>::wrapping_sub(self, rhs)
+            }
+
+            #[inline(always)]
+            fn checked_pow(self, exp: u32) -> Option<Self> {
+                <$t>::checked_pow(self, exp)
+            }
+
+            #[inline(always)]
+            fn pow(self, exp: u32) -> Self {
+                <$t>::pow(self, exp)
+            }
+
+            #[inline(always)]
+            fn div_ceil(self, rhs: Self) -> Self {
+                <$t>::div_ceil(self, rhs)
+            }
+
+            #[inline(always)]
+            fn ilog(self, base: Self) -> u32 {
+                <$t>::ilog(self, base)
+            }
+        }
+    )*};
+}
+
+impl_unsigned!(u32, u64, u128);
+
+/// Number of digits of `n` in `base`. Zero has one digit.
+#[inline]
+pub fn digit_count<T: Unsigned>(n: T, base: u32) -> u32 {
+    debug_assert!(base >= 2);
+    if n == T::ZERO {
+        1
+    } else {
+        n.ilog(T::from_u32(base)) + 1
+    }
+}
+
+/// The digits of `n` in `base`, most significant first.
+pub fn digits<T: Unsigned>(n: T, base: u32) -> impl Iterator<Item = u32> {
+    let b = T::from_u32(base);
+    let len = digit_count(n, base);
+    // base^(len - 1) <= n, so this never overflows.
+    let mut place = b.pow(len - 1);
+    (0..len).map(move |i| {
+        let digit = (n / place % b).as_u32();
+        if i + 1 < len {
+            place = place / b;
+        }
+        digit
+    })
+}
+
+/// `base^exp`, or `None` on overflow.
+#[inline]
+pub fn checked_pow<T: Unsigned>(base: u32, exp: u32) -> Option<T> {
+    T::from_u32(base).checked_pow(exp)
+}
+
+/// Appends the digits of `b` to `a`, e.g. `concat(12, 345, 10) == 12345`.
+#[inline]
+pub fn concat<T: Unsigned>(a: T, b: T, base: u32) -> T {
+    a * T::from_u32(base).pow(digit_count(b, base)) + b
+}
+
+/// Like [`concat`], but returns `None` on overflow.
+pub fn checked_concat<T: Unsigned>(a: T, b: T, base: u32) -> Option<T> {
+    a.checked_mul(checked_pow(base, digit_count(b, base))?)?
+        .checked_add(b)
+}
+
+/// The number made of `len` one-digits, e.g. `repunit(4, 10) == 1111`.
+#[inline]
+pub fn repunit<T: Unsigned>(len: u32, base: u32) -> T {
+    pattern_multiplier(len, 1, base)
+}
+
+/// Like [`repunit`], but returns `None` on overflow.
+pub fn checked_repunit<T: Unsigned>(len: u32, base: u32) -> Option<T> {
+    checked_pattern_multiplier(len, 1, base)
+}
+
+/// The factor turning a `pattern_len`-digit pattern into that pattern repeated
+/// up to `total_len` digits: `(base^total_len - 1) / (base^pattern_len - 1)`.
+/// For example, `pattern_multiplier(6, 2, 10) == 10101` and `12 * 10101 == 121212`.
+///
+/// `pattern_len` must divide `total_len`.
+#[inline]
+pub fn pattern_multiplier<T: Unsigned>(total_len: u32, pattern_len: u32, base: u32) -> T {
+    debug_assert!(pattern_len > 0 && total_len.is_multiple_of(pattern_len));
+    let shift = T::from_u32(base).pow(pattern_len);
+    (0..total_len / pattern_len).fold(T::ZERO, |acc, _| acc * shift + T::ONE)
+}
+
+/// Like [`pattern_multiplier`], but returns `None` on overflow.
+/// Unlike the closed form, this only overflows if the multiplier itself does not fit.
+pub fn checked_pattern_multiplier<T: Unsigned>(
+    total_len: u32,
+    pattern_len: u32,
+    base: u32,
+) -> Option<T> {
+    debug_assert!(pattern_len > 0 && total_len.is_multiple_of(pattern_len));
+    let repetitions = total_len / pattern_len;
+    if repetitions <= 1 {
+        return Some(T::from_u32(repetitions));
+    }
+    let shift: T = checked_pow(base, pattern_len)?;
+    (0..repetitions).try_fold(T::ZERO, |acc, _| {
+        acc.checked_mul(shift)?.checked_add(T::ONE)
+    })
+}
+
+/// Splits `n` into its upper and lower half of digits, or `None` if its digit count is odd.
+/// For example, `halves(123456, 10) == Some((123, 456))`.
+pub fn halves<T: Unsigned>(n: T, base: u32) -> Option<(T, T)> {
+    let len = digit_count(n, base);
+    if !len.is_multiple_of(2) {
+        return None;
+    }
+    let half = T::from_u32(base).pow(len / 2);
+    Some((n / half, n % half))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digit_count_and_digits() {
+        assert_eq!(digit_count(0u64, 10), 1);
+        assert_eq!(digit_count(999u32, 10), 3);
+        assert_eq!(digit_count(1000u32, 10), 4);
+        assert_eq!(digit_count(u128::MAX, 10), 39);
+        assert_eq!(digit_count(0b1011u64, 2), 4);
+        assert_eq!(digits(90210u64, 10).collect::<Vec<_>>(), [9, 0, 2, 1, 0]);
+        assert_eq!(digits(0xbeefu32, 16).collect::<Vec<_>>(), [11, 14, 14, 15]);
+        assert_eq!(digits(0u64, 10).collect::<Vec<_>>(), [0]);
+        assert_eq!(digits(u64::MAX, 10).count(), 20);
+    }
+
+    #[test]
+    fn test_concat() {
+        assert_eq!(concat(12u64, 345, 10), 12345);
+        assert_eq!(concat(1u32, 0, 10), 10);
+        assert_eq!(concat(0b10u32, 0b11, 2), 0b1011);
+        assert_eq!(checked_concat(u32::MAX / 10, 9, 10), None);
+        assert_eq!(checked_concat(42u128, 7, 10), Some(427));
+    }
+
+    #[test]
+    fn test_repunits_and_multipliers() {
+        assert_eq!(repunit::<u64>(4, 10), 1111);
+        assert_eq!(repunit::<u32>(3, 2), 0b111);
+        assert_eq!(pattern_multiplier::<u64>(6, 2, 10), 10101);
+        assert_eq!(pattern_multiplier::<u64>(6, 3, 10), 1001);
+        assert_eq!(pattern_multiplier::<u64>(6, 6, 10), 1);
+
+        // The closed form would need 10^20 here, which does not fit into a u64.
+        assert_eq!(
+            checked_pattern_multiplier::<u64>(20, 10, 10),
+            Some(10_000_000_001)
+        );
+        assert_eq!(
+            checked_repunit::<u64>(20, 10),
+            Some(11_111_111_111_111_111_111)
+        );
+        assert_eq!(checked_repunit::<u64>(21, 10), None);
+        assert_eq!(checked_pattern_multiplier::<u64>(40, 20, 10), None);
+        assert!(checked_pattern_multiplier::<u128>(38, 19, 10).is_some());
+    }
+
+    #[test]
+    fn test_halves() {
+        assert_eq!(halves(123456u64, 10), Some((123, 456)));
+        assert_eq!(halves(12345u64, 10), None);
+        assert_eq!(halves(1010u64, 10), Some((10, 10)));
+    }
+}
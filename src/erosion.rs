@@ -0,0 +1,473 @@
+use crate::grid::{Grid, Neighborhood};
+use rayon::prelude::*;
+
+/// How removals propagate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// Removes cells one at a time from a queue, as soon as they drop below the threshold.
+    Cascade,
+    /// Removes every cell below the threshold at once, one generation at a time.
+    Synchronous,
+}
+
+/// How the initial neighbor counts are computed. Both produce identical results.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Init {
+    /// Scans the grid on the current thread.
+    Sequential,
+    /// Splits the rows into bands that are counted in parallel.
+    Parallel,
+}
+
+impl std::str::FromStr for Init {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sequential" => Ok(Self::Sequential),
+            "parallel" => Ok(Self::Parallel),
+            _ => Err(format!("unknown initialization {s:?}")),
+        }
+    }
+}
+
+/// Repeatedly removes active cells that have fewer than `threshold` active neighbors,
+/// until no such cell is left.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Erosion {
+    /// Active cells with fewer active neighbors than this are removed.
+    pub threshold: u8,
+    /// Relative `(row, col)` offsets of the cells counted as neighbors.
+    pub offsets: Vec<(isize, isize)>,
+    pub mode: Mode,
+    pub init: Init,
+}
+
+/// Result of running an [`Erosion`] to completion.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Outcome {
+    /// Cells removed in the first generation.
+    pub first_wave: usize,
+    /// Cells removed in total.
+    pub removed: usize,
+}
+
+impl Erosion {
+    pub fn new(threshold: u8, neighborhood: Neighborhood) -> Self {
+        Self::with_offsets(threshold, neighborhood.offsets().to_vec())
+    }
+
+    /// An erosion counting the cells at arbitrary `(row, col)` offsets as neighbors.
+    /// The offsets do not need to be symmetric.
+    pub fn with_offsets(threshold: u8, offsets: Vec<(isize, isize)>) -> Self {
+        assert!(offsets.len() <= u8::MAX as usize, "too many neighbors");
+        Self {
+            threshold,
+            offsets,
+            mode: Mode::Cascade,
+            init: Init::Sequential,
+        }
+    }
+
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn init(mut self, init: Init) -> Self {
+        self.init = init;
+        self
+    }
+
+    /// Runs the erosion on the cells of `grid` for which `is_active` holds.
+    pub fn run<T>(&self, grid: &Grid<T>, is_active: impl Fn(&T) -> bool) -> Outcome {
+        let mut state = State::new(self, grid, is_active);
+        let queue = state.initialize_counts_and_queue(self.threshold, self.init);
+        match self.mode {
+            Mode::Cascade => state.cascade(queue, self.threshold, None),
+            Mode::Synchronous => state.synchronous(queue, self.threshold, None),
+        }
+    }
+
+    /// Like [`Erosion::run`], but also records when every cell was removed.
+    pub fn timeline<T>(&self, grid: &Grid<T>, is_active: impl Fn(&T) -> bool) -> Timeline {
+        let mut state = State::new(self, grid, is_active);
+        let queue = state.initialize_counts_and_queue(self.threshold, self.init);
+        let mut generations = vec![0; state.active.cells().len()];
+        let outcome = match self.mode {
+            Mode::Cascade => state.cascade(queue, self.threshold, Some(&mut generations)),
+            Mode::Synchronous => state.synchronous(queue, self.threshold, Some(&mut generations)),
+        };
+
+        let mut timeline = Timeline {
+            generations: Grid::filled(grid.rows(), grid.cols(), None, None),
+            survivors: Grid::filled(grid.rows(), grid.cols(), false, false),
+            outcome,
+        };
+        for r in 0..grid.rows() {
+            for c in 0..grid.cols() {
+                let (from, to) = (state.active.index(r, c), timeline.survivors.index(r, c));
+                timeline.survivors[to] = state.active[from];
+                timeline.generations[to] = (generations[from] > 0).then_some(generations[from]);
+            }
+        }
+        timeline
+    }
+}
+
+/// When every cell of a grid was removed by an [`Erosion`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Timeline {
+    /// The 1-based generation in which each cell was removed, `None` if it never was.
+    pub generations: Grid<Option<u32>>,
+    /// The cells still active at the end.
+    pub survivors: Grid<bool>,
+    pub outcome: Outcome,
+}
+
+impl Timeline {
+    /// Number of generations until the grid was stable.
+    pub fn generation_count(&self) -> u32 {
+        self.generations
+            .cells()
+            .iter()
+            .flatten()
+            .copied()
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Renders surviving cells as `@`, removed cells as their generation in base 36
+    /// (`+` beyond 35) and all other cells as `.`.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for r in 0..self.survivors.rows() {
+            if r > 0 {
+                out.push('\n');
+            }
+            for c in 0..self.survivors.cols() {
+                let index = self.survivors.index(r, c);
+                out.push(match self.generations[index] {
+                    _ if self.survivors[index] => '@',
+                    Some(generation) => char::from_digit(generation, 36).unwrap_or('+'),
+                    None => '.',
+                });
+            }
+        }
+        out
+    }
+}
+
+/// Working copy of the grid, padded wide enough for every neighbor offset.
+struct State {
+    active: Grid<bool>,
+    /// Active neighbor counts for each cell.
+    neighbor_counts: Vec<u8>,
+    /// Flat offsets of the cells counted as neighbors.
+    offsets: Vec<isize>,
+    /// Flat offsets of the cells that count a cell as their neighbor.
+    notify_offsets: Vec<isize>,
+}
+
+impl State {
+    fn new<T>(erosion: &Erosion, grid: &Grid<T>, is_active: impl Fn(&T) -> bool) -> Self {
+        let reach = erosion
+            .offsets
+            .iter()
+            .map(|&(dr, dc)| dr.unsigned_abs().max(dc.unsigned_abs()))
+            .max()
+            .unwrap_or(0)
+            .max(1);
+
+        let mut active = Grid::filled_bordered(grid.rows(), grid.cols(), false, false, reach);
+        for r in 0..grid.rows() {
+            for (c, cell) in grid.row(r).iter().enumerate() {
+                let index = active.index(r, c);
+                active[index] = is_active(cell);
+            }
+        }
+
+        let offsets: Vec<isize> = erosion
+            .offsets
+            .iter()
+            .map(|&offset| active.flat_offset(offset))
+            .collect();
+        let notify_offsets = offsets.iter().map(|&o| -o).collect();
+
+        Self {
+            neighbor_counts: vec![0; active.cells().len()],
+            active,
+            offsets,
+            notify_offsets,
+        }
+    }
+
+    /// Calculates initial neighbor counts for all active cells and returns
+    /// a queue of cells that should be removed in the first generation (count < threshold),
+    /// in flat index order.
+    fn initialize_counts_and_queue(&mut self, threshold: u8, init: Init) -> Vec<usize> {
+        let (rows, stride) = (self.active.rows(), self.active.stride());
+        if rows == 0 {
+            return Vec::new();
+        }
+        let start = self.active.index(0, 0);
+        let band_rows = match init {
+            Init::Sequential => rows,
+            // A few bands per thread to even out the work.
+            Init::Parallel => rows.div_ceil(rayon::current_num_threads() * 4).max(8),
+        };
+
+        let (active, offsets) = (&self.active, &self.offsets);
+        // Every band owns the counts of its rows, starting at its first inner cell.
+        let count_band = |band: usize, counts: &mut [u8]| {
+            let first_row = band * band_rows;
+            let mut queue = Vec::new();
+            for r in first_row..rows.min(first_row + band_rows) {
+                let local = (r - first_row) * stride;
+                for c in 0..active.cols() {
+                    let index = start + r * stride + c;
+                    if active[index] {
+                        let mut count = 0;
+                        for &offset in offsets {
+                            // Safe unchecked access because of padding
+                            if active[index.wrapping_add_signed(offset)] {
+                                count += 1;
+                            }
+                        }
+                        counts[local + c] = count;
+                        if count < threshold {
+                            queue.push(index);
+                        }
+                    }
+                }
+            }
+            queue
+        };
+
+        let counts = &mut self.neighbor_counts[start..];
+        match init {
+            Init::Sequential => count_band(0, counts),
+            Init::Parallel => {
+                let queues: Vec<Vec<usize>> = counts
+                    .par_chunks_mut(band_rows * stride)
+                    .enumerate()
+                    .map(|(band, counts)| count_band(band, counts))
+                    .collect();
+                // Bands are collected in order, so the queue matches the sequential one.
+                queues.concat()
+            }
+        }
+    }
+
+    /// Decrements the counts of the cells that see `index` as a neighbor.
+    /// Calls `on_drop` for every active cell whose count just fell below `threshold`.
+    #[inline(always)]
+    fn notify_removal(&mut self, index: usize, threshold: u8, mut on_drop: impl FnMut(usize)) {
+        for &offset in &self.notify_offsets {
+            let neighbor_index = index.wrapping_add_signed(offset);
+
+            // If neighbor is active, decrement its count
+            if self.active[neighbor_index] {
+                self.neighbor_counts[neighbor_index] -= 1;
+
+                // Cells already below the threshold are queued, so only react
+                // when a count drops to exactly threshold - 1. Nothing is ever
+                // removed with a threshold of 0, so this cannot underflow.
+                if self.neighbor_counts[neighbor_index] == threshold - 1 {
+                    on_drop(neighbor_index);
+                }
+            }
+        }
+    }
+
+    /// Removes queued cells one by one. If `generations` is given, every removed cell
+    /// is assigned the generation it would have been removed in by [`Mode::Synchronous`]:
+    /// one more than the cell whose removal pushed it below the threshold.
+    fn cascade(
+        &mut self,
+        mut queue: Vec<usize>,
+        threshold: u8,
+        mut generations: Option<&mut Vec<u32>>,
+    ) -> Outcome {
+        let first_wave = queue.len();
+        if let Some(generations) = generations.as_deref_mut() {
+            for &index in &queue {
+                generations[index] = 1;
+            }
+        }
+
+        // Process the queue of removed cells
+        let mut head = 0;
+        while head < queue.len() {
+            let current_index = queue[head];
+            head += 1;
+
+            self.active[current_index] = false;
+            self.notify_removal(current_index, threshold, |i| {
+                queue.push(i);
+                if let Some(generations) = generations.as_deref_mut() {
+                    generations[i] = generations[current_index] + 1;
+                }
+            });
+        }
+
+        Outcome {
+            first_wave,
+            removed: queue.len(),
+        }
+    }
+
+    /// Removes all cells below the threshold at once until none are left,
+    /// recording each cell's generation if `generations` is given.
+    fn synchronous(
+        &mut self,
+        mut wave: Vec<usize>,
+        threshold: u8,
+        mut generations: Option<&mut Vec<u32>>,
+    ) -> Outcome {
+        let first_wave = wave.len();
+        let mut removed = 0;
+        let mut next = Vec::new();
+        let mut generation = 0;
+
+        while !wave.is_empty() {
+            removed += wave.len();
+            generation += 1;
+            // Remove the whole generation before updating any counts.
+            for &index in &wave {
+                self.active[index] = false;
+                if let Some(generations) = generations.as_deref_mut() {
+                    generations[index] = generation;
+                }
+            }
+            for &index in &wave {
+                self.notify_removal(index, threshold, |i| next.push(i));
+            }
+            std::mem::swap(&mut wave, &mut next);
+            next.clear();
+        }
+
+        Outcome {
+            first_wave,
+            removed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+
+    const EXAMPLE_INPUT: &str = "..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.";
+
+    fn run(erosion: Erosion) -> Outcome {
+        let grid = Grid::from_ascii(EXAMPLE_INPUT, b'.');
+        erosion.run(&grid, |&c| c == b'@')
+    }
+
+    #[test]
+    fn test_modes_agree() {
+        let expected = Outcome {
+            first_wave: 13,
+            removed: 43,
+        };
+        let erosion = Erosion::new(4, Neighborhood::Moore);
+        assert_eq!(run(erosion.clone()), expected);
+        assert_eq!(run(erosion.mode(Mode::Synchronous)), expected);
+    }
+
+    #[test]
+    fn test_neighborhoods() {
+        let von_neumann = run(Erosion::new(2, Neighborhood::VonNeumann));
+        let custom = run(Erosion::with_offsets(
+            2,
+            Neighborhood::VonNeumann.offsets().to_vec(),
+        ));
+        assert_eq!(von_neumann, custom);
+
+        // Nothing survives if every cell needs more neighbors than it can have.
+        let total = EXAMPLE_INPUT.bytes().filter(|&b| b == b'@').count();
+        let outcome = run(Erosion::new(5, Neighborhood::VonNeumann));
+        assert_eq!((outcome.first_wave, outcome.removed), (total, total));
+        assert_eq!(run(Erosion::new(0, Neighborhood::Moore)).removed, 0);
+    }
+
+    #[test]
+    fn test_asymmetric_offsets() {
+        // Every cell needs an active cell two columns to its right: rows erode from the right.
+        let grid = Grid::from_ascii("@@@@@\n@.@.@", b'.');
+        for mode in [Mode::Cascade, Mode::Synchronous] {
+            let erosion = Erosion::with_offsets(1, vec![(0, 2)]).mode(mode);
+            let outcome = erosion.run(&grid, |&c| c == b'@');
+            assert_eq!(
+                outcome,
+                Outcome {
+                    first_wave: 3,
+                    removed: 8
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_timeline() {
+        let grid = Grid::from_ascii(EXAMPLE_INPUT, b'.');
+        let erosion = Erosion::new(4, Neighborhood::Moore);
+        let timeline = erosion.timeline(&grid, |&c| c == b'@');
+        assert_eq!(
+            timeline.render(),
+            "..11.1121.
+134.2.2.32
+24578.1.33
+2.69@@..2.
+13.@@@@.21
+.24@@@@@.2
+.2.@.@.@@3
+1.4@@.@@@4
+.23@@@@@5.
+1.1.@@@.1."
+        );
+        assert_eq!(timeline.generation_count(), 9);
+        assert_eq!(timeline.outcome, run(erosion.clone()));
+        assert_eq!(
+            erosion
+                .mode(Mode::Synchronous)
+                .timeline(&grid, |&c| c == b'@'),
+            timeline
+        );
+    }
+
+    #[test]
+    fn test_parallel_init_matches_sequential() {
+        let mut rng = SplitMix64::new(39);
+        for (rows, cols) in [(0, 0), (1, 1), (3, 70), (57, 9), (200, 150)] {
+            let density = rng.next_f64();
+            let mut grid = Grid::filled(rows, cols, false, false);
+            for index in grid.indices().collect::<Vec<_>>() {
+                grid[index] = rng.chance(density);
+            }
+            let erosion = Erosion::with_offsets(3, vec![(-2, 1), (0, -1), (1, 1), (2, 2)]);
+
+            let seeds = |init| {
+                let mut state = State::new(&erosion, &grid, |&c| c);
+                let queue = state.initialize_counts_and_queue(erosion.threshold, init);
+                (queue, state.neighbor_counts)
+            };
+            assert_eq!(seeds(Init::Parallel), seeds(Init::Sequential));
+            assert_eq!(
+                erosion.clone().init(Init::Parallel).timeline(&grid, |&c| c),
+                erosion.timeline(&grid, |&c| c)
+            );
+        }
+    }
+}
@@ -0,0 +1,352 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// The set of cells considered adjacent to a given cell.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Neighborhood {
+    /// The 4 orthogonally adjacent cells.
+    VonNeumann,
+    /// The 8 orthogonally and diagonally adjacent cells.
+    Moore,
+}
+
+impl Neighborhood {
+    /// Relative `(row, col)` offsets of the neighbors, in the order of [`Grid::neighbor_offsets`].
+    pub fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            Neighborhood::VonNeumann => &[(-1, 0), (0, -1), (0, 1), (1, 0)],
+            Neighborhood::Moore => &[
+                (-1, -1),
+                (-1, 0),
+                (-1, 1),
+                (0, -1),
+                (0, 1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+            ],
+        }
+    }
+}
+
+/// A flattened grid with padding to avoid boundary checks.
+/// The grid is surrounded by a border of padding cells (1 cell wide by default),
+/// so every neighbor offset of an inner cell is a valid index.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    /// Flattened grid data (padding included).
+    cells: Vec<T>,
+    /// Width of the grid including padding.
+    stride: usize,
+    /// Original height of the input grid.
+    inner_rows: usize,
+    /// Original width of the input grid.
+    inner_cols: usize,
+    /// Width of the padding border on every side.
+    border: usize,
+    /// Pre-calculated relative offsets for the 8 neighbors (top-left to bottom-right).
+    moore_offsets: [isize; 8],
+    /// Pre-calculated relative offsets for the 4 orthogonal neighbors (up, left, right, down).
+    von_neumann_offsets: [isize; 4],
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a `rows` x `cols` grid filled with `value`, surrounded by a 1-cell border of `padding`.
+    pub fn filled(rows: usize, cols: usize, value: T, padding: T) -> Self {
+        Self::filled_bordered(rows, cols, value, padding, 1)
+    }
+
+    /// Like [`Grid::filled`], but with a padding border `border` cells wide.
+    pub fn filled_bordered(rows: usize, cols: usize, value: T, padding: T, border: usize) -> Self {
+        let mut grid = Self::from_cells(
+            vec![padding; (rows + 2 * border) * (cols + 2 * border)],
+            rows,
+            cols,
+            border,
+        );
+        for r in 0..rows {
+            let start = grid.index(r, 0);
+            grid.cells[start..start + cols].fill(value.clone());
+        }
+        grid
+    }
+
+    /// Parses one row per line, mapping every character through `f`.
+    /// Lines shorter than the longest one are filled up with `padding`.
+    pub fn parse(input: &str, padding: T, f: impl FnMut(char) -> T) -> Self {
+        Self::parse_bordered(input, padding, 1, f)
+    }
+
+    /// Like [`Grid::parse`], but with a padding border `border` cells wide.
+    pub fn parse_bordered(
+        input: &str,
+        padding: T,
+        border: usize,
+        mut f: impl FnMut(char) -> T,
+    ) -> Self {
+        let lines: Vec<&str> = input.lines().collect();
+        let inner_cols = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let mut grid =
+            Self::filled_bordered(lines.len(), inner_cols, padding.clone(), padding, border);
+        for (r, line) in lines.iter().enumerate() {
+            let row_start = grid.index(r, 0);
+            for (c, ch) in line.chars().enumerate() {
+                grid.cells[row_start + c] = f(ch);
+            }
+        }
+        grid
+    }
+
+    /// Returns a grid with rows and columns swapped. The padding border is transposed along.
+    pub fn transposed(&self) -> Self {
+        let outer_rows = self.cells.len() / self.stride;
+        let mut cells = Vec::with_capacity(self.cells.len());
+        for c in 0..self.stride {
+            for r in 0..outer_rows {
+                cells.push(self.cells[r * self.stride + c].clone());
+            }
+        }
+        Self::from_cells(cells, self.inner_cols, self.inner_rows, self.border)
+    }
+}
+
+impl Grid<u8> {
+    /// Parses an ASCII grid byte by byte, one row per line.
+    /// Lines shorter than the longest one are filled up with `padding`.
+    pub fn from_ascii(input: &str, padding: u8) -> Self {
+        let lines: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+        let inner_cols = lines.iter().map(|l| l.len()).max().unwrap_or(0);
+        let mut grid = Self::filled(lines.len(), inner_cols, padding, padding);
+        for (r, line) in lines.iter().enumerate() {
+            let row_start = grid.index(r, 0);
+            grid.cells[row_start..row_start + line.len()].copy_from_slice(line);
+        }
+        grid
+    }
+}
+
+impl<T> Grid<T> {
+    fn from_cells(cells: Vec<T>, inner_rows: usize, inner_cols: usize, border: usize) -> Self {
+        let stride = inner_cols + 2 * border;
+        debug_assert_eq!(cells.len(), (inner_rows + 2 * border) * stride);
+
+        let s = stride as isize;
+        let moore_offsets = [-s - 1, -s, -s + 1, -1, 1, s - 1, s, s + 1];
+        let von_neumann_offsets = [-s, -1, 1, s];
+
+        Self {
+            cells,
+            stride,
+            inner_rows,
+            inner_cols,
+            border,
+            moore_offsets,
+            von_neumann_offsets,
+        }
+    }
+
+    /// Height of the grid without padding.
+    pub fn rows(&self) -> usize {
+        self.inner_rows
+    }
+
+    /// Width of the grid without padding.
+    pub fn cols(&self) -> usize {
+        self.inner_cols
+    }
+
+    /// Width of the grid including padding, i.e. the index distance between two rows.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Width of the padding border on every side.
+    pub fn border(&self) -> usize {
+        self.border
+    }
+
+    /// All cells in flat index order, padding included.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// Flat index of the inner cell at `(row, col)`.
+    #[inline(always)]
+    pub fn index(&self, row: usize, col: usize) -> usize {
+        (row + self.border) * self.stride + col + self.border
+    }
+
+    /// Inner `(row, col)` coordinates of a flat index, or `None` for padding cells.
+    pub fn coords(&self, index: usize) -> Option<(usize, usize)> {
+        let row = (index / self.stride).checked_sub(self.border)?;
+        let col = (index % self.stride).checked_sub(self.border)?;
+        (row < self.inner_rows && col < self.inner_cols).then_some((row, col))
+    }
+
+    /// Returns the inner cell at `(row, col)`, or `None` if it is out of bounds.
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        (row < self.inner_rows && col < self.inner_cols).then(|| &self.cells[self.index(row, col)])
+    }
+
+    /// Flat indices of all inner cells, row by row.
+    pub fn indices(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.inner_rows).flat_map(move |r| {
+            let start = self.index(r, 0);
+            start..start + self.inner_cols
+        })
+    }
+
+    /// Relative index offsets of the neighbors in the given neighborhood.
+    #[inline(always)]
+    pub fn neighbor_offsets(&self, neighborhood: Neighborhood) -> &[isize] {
+        match neighborhood {
+            Neighborhood::VonNeumann => &self.von_neumann_offsets,
+            Neighborhood::Moore => &self.moore_offsets,
+        }
+    }
+
+    /// Converts a relative `(row, col)` offset into a flat index offset.
+    #[inline(always)]
+    pub fn flat_offset(&self, (dr, dc): (isize, isize)) -> isize {
+        dr * self.stride as isize + dc
+    }
+
+    /// Flat indices of the neighbors of `index`. Padding makes this safe for every inner cell.
+    pub fn neighbors(
+        &self,
+        index: usize,
+        neighborhood: Neighborhood,
+    ) -> impl Iterator<Item = usize> + '_ {
+        self.neighbor_offsets(neighborhood)
+            .iter()
+            .map(move |&offset| index.wrapping_add_signed(offset))
+    }
+
+    /// The inner cells of row `row`.
+    pub fn row(&self, row: usize) -> &[T] {
+        let start = self.index(row, 0);
+        &self.cells[start..start + self.inner_cols]
+    }
+
+    /// All inner rows, top to bottom.
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.inner_rows).map(|r| self.row(r))
+    }
+
+    /// The inner cells of column `col`, top to bottom.
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> + '_ {
+        (0..self.inner_rows).map(move |r| &self.cells[self.index(r, col)])
+    }
+
+    /// All inner columns, left to right.
+    pub fn iter_columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.inner_cols).map(|c| self.column(c))
+    }
+
+    /// Renders the inner cells, one line per row, mapping every cell through `f`.
+    pub fn render(&self, mut f: impl FnMut(&T) -> char) -> String {
+        let mut out = String::with_capacity(self.inner_rows * (self.inner_cols + 1));
+        for (r, row) in self.iter_rows().enumerate() {
+            if r > 0 {
+                out.push('\n');
+            }
+            out.extend(row.iter().map(&mut f));
+        }
+        out
+    }
+}
+
+impl<T> Index<usize> for Grid<T> {
+    type Output = T;
+
+    #[inline(always)]
+    fn index(&self, index: usize) -> &T {
+        &self.cells[index]
+    }
+}
+
+impl<T> IndexMut<usize> for Grid<T> {
+    #[inline(always)]
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.cells[index]
+    }
+}
+
+impl<T: Copy + Into<char>> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render(|&cell| cell.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "..@
+@@.
+.@@
+@..";
+
+    #[test]
+    fn test_parse_and_display() {
+        let grid = Grid::from_ascii(EXAMPLE_INPUT, b'.');
+        assert_eq!((grid.rows(), grid.cols(), grid.stride()), (4, 3, 5));
+        assert_eq!(grid.to_string(), EXAMPLE_INPUT);
+        assert_eq!(
+            Grid::parse(EXAMPLE_INPUT, '.', |c| c).to_string(),
+            grid.to_string()
+        );
+    }
+
+    #[test]
+    fn test_ragged_lines_are_padded() {
+        let grid = Grid::parse("ab\nc\n", ' ', |c| c);
+        assert_eq!(grid.to_string(), "ab\nc ");
+        assert_eq!(grid.get(1, 1), Some(&' '));
+        assert_eq!(grid.get(2, 0), None);
+    }
+
+    #[test]
+    fn test_index_coords_roundtrip() {
+        let grid = Grid::parse_bordered(EXAMPLE_INPUT, '.', 2, |c| c);
+        for (i, index) in grid.indices().enumerate() {
+            assert_eq!(grid.coords(index), Some((i / 3, i % 3)));
+        }
+        assert_eq!(grid.coords(0), None);
+        assert_eq!(grid.coords(grid.index(0, 3)), None);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::from_ascii(EXAMPLE_INPUT, b'.');
+        let center = grid.index(1, 1);
+        let active = |n| {
+            grid.neighbors(center, n)
+                .filter(|&i| grid[i] == b'@')
+                .count()
+        };
+        assert_eq!(active(Neighborhood::Moore), 4);
+        assert_eq!(active(Neighborhood::VonNeumann), 2);
+
+        // Corner cells only see padding outside the grid.
+        let corner = grid.index(0, 0);
+        assert_eq!(
+            grid.neighbors(corner, Neighborhood::Moore)
+                .filter(|&i| grid.coords(i).is_none())
+                .count(),
+            5
+        );
+    }
+
+    #[test]
+    fn test_rows_columns_transposed() {
+        let grid = Grid::from_ascii(EXAMPLE_INPUT, b'.');
+        assert_eq!(grid.row(1), b"@@.");
+        assert_eq!(grid.column(0).copied().collect::<Vec<_>>(), b".@.@");
+        assert_eq!(grid.iter_columns().count(), 3);
+
+        let transposed = grid.transposed();
+        assert_eq!(transposed.to_string(), ".@.@\n.@@.\n@.@.");
+        assert_eq!(transposed.transposed(), grid);
+    }
+}
@@ -0,0 +1,322 @@
+/// Integer types usable as interval bounds.
+pub trait Discrete: Copy + Ord {
+    /// The next value, or `None` at the maximum.
+    fn succ(self) -> Option<Self>;
+    /// The previous value, or `None` at the minimum.
+    fn pred(self) -> Option<Self>;
+    /// Number of values in `start..=end`, assuming `start <= end`. Saturates at
+    /// `u128::MAX`, which only all of `u128` or `i128` exceeds.
+    fn span(start: Self, end: Self) -> u128;
+}
+
+macro_rules! impl_discrete {
+    ($($t:ty),*) => {$(
+        impl Discrete for $t {
+            #[inline(always)]
+            fn succ(self) -> Option<Self> {
+                self.checked_add(1)
+            }
+
+            #[inline(always)]
+            fn pred(self) -> Option<Self> {
+                self.checked_sub(1)
+            }
+
+            #[inline(always)]
+            fn span(start: Self, end: Self) -> u128 {
+                (end.abs_diff(start) as u128).saturating_add(1)
+            }
+        }
+    )*};
+}
+
+impl_discrete!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+/// A non-empty interval of integers with inclusive bounds. Only the checked
+/// constructors create one, so `start <= end` always holds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    start: T,
+    end: T,
+}
+
+impl<T: Discrete> Interval<T> {
+    /// The interval `start..=end`, or `None` if it is empty.
+    pub fn inclusive(start: T, end: T) -> Option<Self> {
+        (start <= end).then_some(Self { start, end })
+    }
+
+    /// The interval `start..end`, or `None` if it is empty.
+    pub fn half_open(start: T, end: T) -> Option<Self> {
+        Self::inclusive(start, end.pred()?)
+    }
+
+    /// The smallest integer in the interval.
+    pub fn start(&self) -> T {
+        self.start
+    }
+
+    /// The largest integer in the interval.
+    pub fn end(&self) -> T {
+        self.end
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    /// Number of integers in the interval, saturating at `u128::MAX`. Intervals are
+    /// never empty.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> u128 {
+        T::span(self.start, self.end)
+    }
+}
+
+/// A set of integers stored as sorted, disjoint and non-adjacent intervals.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Discrete> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Discrete> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut intervals: Vec<Interval<T>> = iter.into_iter().collect();
+        intervals.sort_unstable_by_key(|iv| iv.start);
+        Self::from_sorted(intervals)
+    }
+}
+
+impl<T: Discrete> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+
+    /// Merges intervals that are already sorted by start.
+    fn from_sorted(sorted: impl IntoIterator<Item = Interval<T>>) -> Self {
+        let mut intervals: Vec<Interval<T>> = Vec::new();
+        for interval in sorted {
+            if let Some(last) = intervals.last_mut() {
+                // Since we are covering integers, if interval.start <= last.end + 1,
+                // they form a contiguous block.
+                if last.end.succ().is_none_or(|next| interval.start <= next) {
+                    last.end = last.end.max(interval.end);
+                    continue;
+                }
+            }
+            intervals.push(interval);
+        }
+        Self { intervals }
+    }
+
+    /// Adds an interval, merging it with any overlapping or adjacent ones.
+    pub fn insert(&mut self, interval: Interval<T>) {
+        // First interval that could touch the new one, i.e. ends at or after interval.start - 1.
+        let lo = self
+            .intervals
+            .partition_point(|iv| iv.end.succ().is_some_and(|next| next < interval.start));
+        // One past the last interval starting at or before interval.end + 1.
+        let hi = self
+            .intervals
+            .partition_point(|iv| interval.end.succ().is_none_or(|next| iv.start <= next));
+
+        let mut merged = interval;
+        if lo < hi {
+            merged.start = merged.start.min(self.intervals[lo].start);
+            merged.end = merged.end.max(self.intervals[hi - 1].end);
+        }
+        self.intervals.splice(lo..hi, [merged]);
+    }
+
+    /// The normalized intervals in ascending order.
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Interval<T>> + '_ {
+        self.intervals.iter().copied()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Total number of integers covered by the set, saturating at `u128::MAX`.
+    pub fn len(&self) -> u128 {
+        self.intervals
+            .iter()
+            .map(Interval::len)
+            .fold(0, u128::saturating_add)
+    }
+
+    /// Membership query in O(log n) via binary search.
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.intervals.partition_point(|iv| iv.end < value);
+        self.intervals.get(i).is_some_and(|iv| iv.start <= value)
+    }
+
+    /// The uncovered intervals strictly between the first and last covered value.
+    pub fn gaps(&self) -> impl Iterator<Item = Interval<T>> + '_ {
+        self.intervals.windows(2).map(|pair| Interval {
+            // Normalized intervals are non-adjacent, so every gap is non-empty.
+            start: pair[0].end.succ().unwrap(),
+            end: pair[1].start.pred().unwrap(),
+        })
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut a = self.intervals.iter().peekable();
+        let mut b = other.intervals.iter().peekable();
+        let sorted = std::iter::from_fn(|| match (a.peek(), b.peek()) {
+            (Some(x), Some(y)) if x.start <= y.start => a.next(),
+            (Some(_), Some(_)) => b.next(),
+            (Some(_), None) => a.next(),
+            (None, _) => b.next(),
+        });
+        Self::from_sorted(sorted.copied())
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (x, y) = (self.intervals[i], other.intervals[j]);
+            if let Some(overlap) = Interval::inclusive(x.start.max(y.start), x.end.min(y.end)) {
+                intervals.push(overlap);
+            }
+            // Advance whichever interval ends first; it cannot overlap anything further.
+            if x.end < y.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { intervals }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let mut j = 0;
+        for &interval in &self.intervals {
+            let mut current = Some(interval);
+            // Skip removals that end before this interval starts.
+            while j < other.intervals.len() && other.intervals[j].end < interval.start {
+                j += 1;
+            }
+            let mut k = j;
+            while let (Some(rest), Some(cut)) = (current, other.intervals.get(k)) {
+                if cut.start > rest.end {
+                    break;
+                }
+                if let Some(before) = cut
+                    .start
+                    .pred()
+                    .and_then(|p| Interval::inclusive(rest.start, p))
+                {
+                    intervals.push(before);
+                }
+                current = cut
+                    .end
+                    .succ()
+                    .and_then(|s| Interval::inclusive(s, rest.end));
+                k += 1;
+            }
+            intervals.extend(current);
+        }
+        Self { intervals }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[(u64, u64)]) -> IntervalSet<u64> {
+        ranges
+            .iter()
+            .map(|&(s, e)| Interval::inclusive(s, e).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_normalization() {
+        let merged = set(&[(3, 5), (10, 14), (16, 20), (12, 18), (6, 6)]);
+        assert_eq!(merged, set(&[(3, 6), (10, 20)]));
+        assert_eq!(merged.len(), 15);
+        assert_eq!(set(&[(0, u64::MAX), (5, 7)]).len(), 1 << 64);
+        assert_eq!(Interval::half_open(4u64, 4), None);
+        assert_eq!(Interval::half_open(4u64, 6), Interval::inclusive(4, 5));
+        assert_eq!(Interval::inclusive(5u64, 4), None);
+        let interval = Interval::inclusive(4u64, 9).unwrap();
+        assert_eq!((interval.start(), interval.end()), (4, 9));
+    }
+
+    #[test]
+    fn test_wide_bounds() {
+        fn len<T: Discrete>(start: T, end: T) -> u128 {
+            Interval::inclusive(start, end).unwrap().len()
+        }
+        assert_eq!(len(i128::MIN, -1i128), 1 << 127);
+        assert_eq!(len(1, u128::MAX), u128::MAX);
+        // One more than fits, so the length saturates.
+        assert_eq!(len(0, u128::MAX), u128::MAX);
+        assert_eq!(len(i128::MIN, i128::MAX), u128::MAX);
+        let halves = [(0, u128::MAX / 2), (u128::MAX / 2 + 2, u128::MAX)]
+            .map(|(start, end)| Interval::inclusive(start, end).unwrap());
+        assert_eq!(IntervalSet::from_iter(halves).len(), u128::MAX);
+    }
+
+    #[test]
+    fn test_insert_matches_collect() {
+        let ranges = [
+            (10, 14),
+            (3, 5),
+            (20, 22),
+            (6, 9),
+            (30, 40),
+            (15, 31),
+            (0, 0),
+        ];
+        let mut inserted = IntervalSet::new();
+        for &(s, e) in &ranges {
+            inserted.insert(Interval::inclusive(s, e).unwrap());
+        }
+        assert_eq!(inserted, set(&ranges));
+        assert_eq!(inserted, set(&[(0, 0), (3, 40)]));
+    }
+
+    #[test]
+    fn test_contains_and_gaps() {
+        let s = set(&[(3, 5), (10, 14), (16, 20)]);
+        let hits: Vec<u64> = [1, 5, 8, 11, 17, 32]
+            .into_iter()
+            .filter(|&x| s.contains(x))
+            .collect();
+        assert_eq!(hits, [5, 11, 17]);
+        assert_eq!(
+            s.gaps().collect::<IntervalSet<_>>(),
+            set(&[(6, 9), (15, 15)])
+        );
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[(0, 10), (20, 30)]);
+        let b = set(&[(5, 25), (28, 40)]);
+        assert_eq!(a.union(&b), set(&[(0, 40)]));
+        assert_eq!(a.intersection(&b), set(&[(5, 10), (20, 25), (28, 30)]));
+        assert_eq!(a.difference(&b), set(&[(0, 4), (26, 27)]));
+        assert_eq!(b.difference(&a), set(&[(11, 19), (31, 40)]));
+        assert_eq!(a.difference(&set(&[(0, u64::MAX)])), IntervalSet::new());
+    }
+}
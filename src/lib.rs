@@ -1,30 +1,20 @@
-use proc_macro::TokenStream;
-use quote::quote;
-use syn::{Ident, ItemFn, LitInt, parse_macro_input};
+pub use aoc_macros::main;
 
-#[proc_macro_attribute]
-pub fn main(args: TokenStream, input: TokenStream) -> TokenStream {
-    let day = parse_macro_input!(args as LitInt);
-    let input_path = format!("../../inputs/{}.in", day);
-
-    let mut aoc_solution = parse_macro_input!(input as ItemFn);
-    aoc_solution.sig.ident = Ident::new("aoc_solution", aoc_solution.sig.ident.span());
-
-    let tokens = quote! {
-      const INPUT: &str = include_str!(#input_path);
-      #aoc_solution
-      fn main() {
-        let now = ::std::time::Instant::now();
-        let (p1, p2) = aoc_solution(INPUT.trim_end());
-        let elapsed = now.elapsed();
-        println!("Part one: {}", p1);
-        println!("Part two: {}", p2);
-        if elapsed.as_millis() > 0 {
-          println!("Time: {}ms", elapsed.as_millis());
-        } else {
-          println!("Time: {}μs", elapsed.as_micros());
-        }
-      }
-    };
-    TokenStream::from(tokens)
-}
+pub mod bitgrid;
+pub mod columns;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod digits;
+pub mod erosion;
+pub mod grid;
+pub mod interval;
+pub mod parse;
+pub mod pool;
+pub mod rng;
+pub mod select;
+pub mod synthetic;
+pub mod visualize;
@@ -1,5 +1,16 @@
+use aoc::{pool, synthetic};
 use itertools::Itertools;
-use std::{error::Error, fs, process::Command};
+use std::{env, error::Error, fs, path::PathBuf, process, process::Command, thread};
+
+const USAGE: &str = "usage: aoc
+       aoc gen DAY [--scale N] [--seed S]
+       aoc scale [DAY...] [--scales N,N,...] [--seed S]
+       aoc threads [DAY...] [--max N] [--runs N] [--scale N] [--warm]";
+
+fn usage() -> ! {
+    eprintln!("{USAGE}");
+    process::exit(2);
+}
 
 fn extract_microseconds(output: &str) -> Result<usize, Box<dyn Error>> {
     let out = output.lines().last().unwrap();
@@ -11,17 +22,219 @@ fn extract_microseconds(output: &str) -> Result<usize, Box<dyn Error>> {
     Ok(time)
 }
 
+/// Renders a time the way the days print it.
+fn format_microseconds(time: usize) -> String {
+    if time >= 1000 {
+        format!("{}ms", time / 1000)
+    } else {
+        format!("{time}μs")
+    }
+}
+
+/// Runs a day's binary in release mode with extra environment variables and returns
+/// its output.
+fn run_day(day: &str, args: &[&str], envs: &[(&str, String)]) -> Result<String, Box<dyn Error>> {
+    let cmd = Command::new("cargo")
+        .args(["run", "--release", "--bin", day, "--"])
+        .args(args)
+        .envs(envs.iter().map(|(key, value)| (key, value)))
+        .output()?;
+    if !cmd.status.success() {
+        return Err(format!("day {day} failed: {}", String::from_utf8_lossy(&cmd.stderr)).into());
+    }
+    Ok(String::from_utf8(cmd.stdout)?)
+}
+
+/// Removes `--name VALUE` from the arguments and parses the value.
+fn take_flag<T>(args: &mut Vec<String>, name: &str) -> Result<Option<T>, Box<dyn Error>>
+where
+    T: std::str::FromStr,
+    T::Err: Error + 'static,
+{
+    let Some(index) = args.iter().position(|arg| arg == name) else {
+        return Ok(None);
+    };
+    if index + 1 == args.len() {
+        return Err(format!("{name} needs a value").into());
+    }
+    let value = args.remove(index + 1);
+    args.remove(index);
+    Ok(Some(value.parse()?))
+}
+
+fn parse_day(day: &str) -> u32 {
+    match day.parse() {
+        Ok(day @ 1..=25) => day,
+        _ => usage(),
+    }
+}
+
+/// Input sizes for the scaling curve of a day. Day 4's scale is the side of the grid.
+fn default_scales(day: u32) -> Vec<usize> {
+    match day {
+        4 => vec![100, 300, 1_000, 3_000],
+        _ => vec![1_000, 10_000, 100_000, 1_000_000],
+    }
+}
+
+/// The days named in `args`, or all of them.
+fn select_days(days: &[String], args: &[String]) -> Vec<String> {
+    if args.is_empty() {
+        days.to_vec()
+    } else {
+        args.iter()
+            .map(|day| format!("{:02}", parse_day(day)))
+            .collect()
+    }
+}
+
+/// Writes a generated input for `day` to a temporary file.
+fn write_generated(day: &str, scale: usize, seed: u64) -> Result<Option<PathBuf>, Box<dyn Error>> {
+    let Some(input) = synthetic::generate(parse_day(day), scale, seed) else {
+        return Ok(None);
+    };
+    let path = env::temp_dir().join(format!("aoc-{day}-{scale}-{seed}.in"));
+    fs::write(&path, input)?;
+    Ok(Some(path))
+}
+
+/// `aoc gen DAY`: prints a generated input for the day.
+fn generate(mut args: Vec<String>) -> Result<(), Box<dyn Error>> {
+    let scale = match take_flag(&mut args, "--scale")?.unwrap_or(1_000) {
+        0 => return Err("--scale must be at least 1".into()),
+        scale => scale,
+    };
+    let seed = take_flag(&mut args, "--seed")?.unwrap_or(0);
+    let [day] = args.as_slice() else {
+        usage();
+    };
+    let day = parse_day(day);
+    let input = synthetic::generate(day, scale, seed)
+        .ok_or_else(|| format!("no generator for day {day}"))?;
+    println!("{input}");
+    Ok(())
+}
+
+/// `aoc scale [DAY...]`: times each day on generated inputs of increasing size.
+fn scale(days: &[String], mut args: Vec<String>) -> Result<(), Box<dyn Error>> {
+    let scales: Option<String> = take_flag(&mut args, "--scales")?;
+    let scales = scales
+        .map(|scales| {
+            scales
+                .split(',')
+                .map(str::parse)
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()?;
+    if scales.iter().flatten().any(|&scale| scale == 0) {
+        return Err("--scales must be at least 1".into());
+    }
+    let seed = take_flag(&mut args, "--seed")?.unwrap_or(0);
+
+    println!("| Day | Scale | Input | Time |");
+    println!("| --- | ---: | ---: | ---: |");
+    for day in &select_days(days, &args) {
+        for &scale in scales.as_ref().unwrap_or(&default_scales(parse_day(day))) {
+            let Some(path) = write_generated(day, scale, seed)? else {
+                continue;
+            };
+            let size = fs::metadata(&path)?.len();
+            let output = run_day(day, &["--input", path.to_str().unwrap()], &[]);
+            fs::remove_file(&path)?;
+            let time = extract_microseconds(&output?)?;
+            println!(
+                "| {day} | {scale} | {}KiB | {} |",
+                size.div_ceil(1024),
+                format_microseconds(time)
+            );
+        }
+    }
+    Ok(())
+}
+
+/// `aoc threads [DAY...]`: times each day sequentially and with 1 to `--max` rayon
+/// threads, taking the median of `--runs` runs, on the real input or a generated one of
+/// `--scale`.
+///
+/// The sequential baseline always runs with `AOC_THREADS=sequential`, a one-thread pool
+/// on the main thread that is built before timing starts. By default the other rows set
+/// `RAYON_NUM_THREADS`, so their pool starts lazily inside the timed solve and the
+/// speedup includes its startup. With `--warm` they set `AOC_THREADS` instead, so every
+/// pool is running before timing and the speedup compares solving alone.
+fn threads(days: &[String], mut args: Vec<String>) -> Result<(), Box<dyn Error>> {
+    let cores = thread::available_parallelism().map_or(1, usize::from);
+    let max = take_flag(&mut args, "--max")?.unwrap_or(cores);
+    let runs = take_flag(&mut args, "--runs")?.unwrap_or(5usize).max(1);
+    let scale: Option<usize> = take_flag(&mut args, "--scale")?;
+    let warm = args.iter().position(|arg| arg == "--warm");
+    let warm = warm.map(|index| args.remove(index)).is_some();
+    let threads_var = if warm {
+        pool::THREADS_VAR
+    } else {
+        "RAYON_NUM_THREADS"
+    };
+
+    println!("| Day | Threads | Time | Speedup | Efficiency |");
+    println!("| --- | ---: | ---: | ---: | ---: |");
+    for day in &select_days(days, &args) {
+        let path = match scale {
+            Some(scale) => write_generated(day, scale, 0)?,
+            None => None,
+        };
+        let input = path
+            .iter()
+            .flat_map(|path| ["--input", path.to_str().unwrap()]);
+        let input: Vec<&str> = input.collect();
+        let median = |envs: &[(&str, String)]| -> Result<usize, Box<dyn Error>> {
+            let mut times = (0..runs)
+                .map(|_| extract_microseconds(&run_day(day, &input, envs)?))
+                .collect::<Result<Vec<_>, _>>()?;
+            times.sort_unstable();
+            Ok(times[runs / 2])
+        };
+
+        // Speedups are relative to the sequential pool, whose startup is never timed.
+        let sequential = median(&[(pool::THREADS_VAR, "sequential".to_string())])?;
+        println!(
+            "| {day} | sequential | {} | 1.00 | |",
+            format_microseconds(sequential)
+        );
+        for count in 1..=max {
+            let time = median(&[(threads_var, count.to_string())])?;
+            let speedup = sequential as f64 / time.max(1) as f64;
+            println!(
+                "| {day} | {count} | {} | {speedup:.2} | {:.0}% |",
+                format_microseconds(time),
+                100.0 * speedup / count as f64
+            );
+        }
+        if let Some(path) = path {
+            fs::remove_file(path)?;
+        }
+    }
+    Ok(())
+}
+
 fn main() -> Result<(), Box<dyn Error>> {
     let days = fs::read_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/src/bin/"))?
         .filter_map(|p| p.ok()?.path().file_stem()?.to_str().map(str::to_string))
         .sorted()
         .collect::<Vec<_>>();
+
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        let command = args.remove(0);
+        return match command.as_str() {
+            "gen" => generate(args),
+            "scale" => scale(&days, args),
+            "threads" => threads(&days, args),
+            _ => usage(),
+        };
+    }
+
     let mut total_time = 0;
     for day in &days {
-        let cmd = Command::new("cargo")
-            .args(["run", "--release", "--bin", day])
-            .output()?;
-        let output = String::from_utf8(cmd.stdout)?;
+        let output = run_day(day, &[], &[])?;
         println!("Day {}:\n{}", day, output);
         total_time += extract_microseconds(&output)?;
     }
@@ -0,0 +1,509 @@
+use std::error::Error;
+use std::fmt;
+
+/// What went wrong while parsing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// A digit (or sign) was expected.
+    ExpectedNumber,
+    /// The number does not fit into the requested integer type.
+    Overflow,
+    /// A specific byte was expected.
+    Expected(u8),
+    /// The input ended too early.
+    UnexpectedEnd,
+    /// Input remained after a complete value.
+    TrailingInput,
+    /// A token that is not one of the accepted ones.
+    UnknownToken,
+    /// A range whose end lies before its start.
+    InvalidRange,
+}
+
+/// A parse error with a 1-based line and column pointing at the offending byte.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub kind: ErrorKind,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::ExpectedNumber => write!(f, "expected a number"),
+            ErrorKind::Overflow => write!(f, "number out of range"),
+            ErrorKind::Expected(b) => write!(f, "expected {:?}", *b as char),
+            ErrorKind::UnexpectedEnd => write!(f, "unexpected end of input"),
+            ErrorKind::TrailingInput => write!(f, "unexpected trailing input"),
+            ErrorKind::UnknownToken => write!(f, "unknown token"),
+            ErrorKind::InvalidRange => write!(f, "range ends before it starts"),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.kind)
+    }
+}
+
+impl Error for ParseError {}
+
+/// Errors that may point at a position in the input.
+pub trait Located: fmt::Display {
+    /// The 1-based line and column of the offending character, if there is one.
+    fn location(&self) -> Option<(usize, usize)>;
+}
+
+impl Located for ParseError {
+    fn location(&self) -> Option<(usize, usize)> {
+        Some((self.line, self.column))
+    }
+}
+
+/// Characters of context kept on either side of the offending one in a snippet.
+const SNIPPET_CONTEXT: usize = 30;
+
+/// A day's error together with the input line it points at, rendered with a caret
+/// under the offending character:
+///
+/// ```text
+/// day 01: 2:1: unknown token
+///   |
+/// 2 | X5
+///   | ^
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InputError {
+    pub day: u32,
+    pub message: String,
+    /// 1-based line and column, if the error has a position.
+    pub location: Option<(usize, usize)>,
+    /// The offending line, cut down to the context around the column on long lines.
+    pub snippet: String,
+    /// 0-based character offset of the caret within `snippet`.
+    pub caret: usize,
+}
+
+impl InputError {
+    pub fn new(day: u32, input: &str, err: &(impl Located + ?Sized)) -> Self {
+        let location = err.location();
+        let (snippet, caret) = location
+            .and_then(|(line, column)| Some((input.lines().nth(line.checked_sub(1)?)?, column)))
+            .map_or((String::new(), 0), |(line, column)| {
+                // Errors at the end of a line point just past its last character.
+                let chars = line.chars().count();
+                let column = column.saturating_sub(1).min(chars);
+                let start = column.saturating_sub(SNIPPET_CONTEXT);
+                let end = chars.min(column + SNIPPET_CONTEXT + 1);
+                let mut snippet: String = line.chars().skip(start).take(end - start).collect();
+                let mut caret = column - start;
+                if start > 0 {
+                    snippet.insert_str(0, "...");
+                    caret += 3;
+                }
+                if end < chars {
+                    snippet.push_str("...");
+                }
+                (snippet, caret)
+            });
+        Self {
+            day,
+            message: err.to_string(),
+            location,
+            snippet,
+            caret,
+        }
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "day {:02}: {}", self.day, self.message)?;
+        let Some((line, _)) = self.location.filter(|_| !self.snippet.is_empty()) else {
+            return Ok(());
+        };
+        let gutter = " ".repeat(line.to_string().len());
+        write!(f, "\n{gutter} |\n{line} | {}\n{gutter} | ", self.snippet)?;
+        write!(f, "{}^", " ".repeat(self.caret))
+    }
+}
+
+impl Error for InputError {}
+
+/// Integer types that can be scanned directly from bytes.
+pub trait Integer: Copy {
+    /// Scans an integer at the start of `bytes`, returning it and the number of bytes consumed.
+    fn scan(bytes: &[u8]) -> Result<(Self, usize), ErrorKind>;
+}
+
+macro_rules! impl_unsigned {
+    ($($t:ty),*) => {$(
+        impl Integer for $t {
+            #[inline]
+            fn scan(bytes: &[u8]) -> Result<(Self, usize), ErrorKind> {
+                // Up to MAX.ilog10() digits always fit, so only longer numbers need checks.
+                const SAFE_DIGITS: usize = <$t>::MAX.ilog10() as usize;
+                let mut value: $t = 0;
+                let mut len = 0;
+                while let Some(&b) = bytes.get(len) {
+                    let d = b.wrapping_sub(b'0');
+                    if d > 9 {
+                        break;
+                    }
+                    value = if len < SAFE_DIGITS {
+                        value * 10 + d as $t
+                    } else {
+                        value
+                            .checked_mul(10)
+                            .and_then(|v| v.checked_add(d as $t))
+                            .ok_or(ErrorKind::Overflow)?
+                    };
+                    len += 1;
+                }
+                if len == 0 {
+                    return Err(ErrorKind::ExpectedNumber);
+                }
+                Ok((value, len))
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_signed {
+    ($($t:ty),*) => {$(
+        impl Integer for $t {
+            #[inline]
+            fn scan(bytes: &[u8]) -> Result<(Self, usize), ErrorKind> {
+                let (negative, sign) = match bytes.first() {
+                    Some(b'-') => (true, 1),
+                    Some(b'+') => (false, 1),
+                    _ => (false, 0),
+                };
+                let digits = bytes[sign..].iter().take_while(|b| b.is_ascii_digit()).count();
+                if digits == 0 {
+                    return Err(ErrorKind::ExpectedNumber);
+                }
+                // Accumulate towards the sign so that MIN does not overflow.
+                let mut value: $t = 0;
+                for &b in &bytes[sign..sign + digits] {
+                    let d = (b - b'0') as $t;
+                    value = value
+                        .checked_mul(10)
+                        .and_then(|v| if negative { v.checked_sub(d) } else { v.checked_add(d) })
+                        .ok_or(ErrorKind::Overflow)?;
+                }
+                Ok((value, sign + digits))
+            }
+        }
+    )*};
+}
+
+impl_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_signed!(i8, i16, i32, i64, i128, isize);
+
+/// A position-tracking reader over the input bytes.
+/// Errors carry the line and column of the byte the cursor was looking at.
+#[derive(Clone, Debug)]
+pub struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    /// 0-based line of the current position.
+    line: usize,
+    /// Byte offset where the current line starts.
+    line_start: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self::at_line(input, 0)
+    }
+
+    /// A cursor over `input` that reports errors as if it started on 0-based line `line`.
+    pub fn at_line(input: &'a str, line: usize) -> Self {
+        Self {
+            bytes: input.as_bytes(),
+            pos: 0,
+            line,
+            line_start: 0,
+        }
+    }
+
+    /// An error of the given kind at the current position.
+    pub fn error(&self, kind: ErrorKind) -> ParseError {
+        ParseError {
+            kind,
+            line: self.line + 1,
+            column: self.pos - self.line_start + 1,
+        }
+    }
+
+    #[inline]
+    pub fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    #[inline]
+    pub fn is_eof(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+
+    /// The unconsumed input.
+    pub fn rest(&self) -> &'a [u8] {
+        &self.bytes[self.pos..]
+    }
+
+    #[inline]
+    fn advance(&mut self, n: usize) {
+        for &b in &self.bytes[self.pos..self.pos + n] {
+            self.pos += 1;
+            if b == b'\n' {
+                self.line += 1;
+                self.line_start = self.pos;
+            }
+        }
+    }
+
+    /// Consumes one byte.
+    pub fn next_byte(&mut self) -> Result<u8, ParseError> {
+        let b = self.peek().ok_or(self.error(ErrorKind::UnexpectedEnd))?;
+        self.advance(1);
+        Ok(b)
+    }
+
+    /// Parses an integer at the current position.
+    #[inline]
+    pub fn int<T: Integer>(&mut self) -> Result<T, ParseError> {
+        if self.is_eof() {
+            return Err(self.error(ErrorKind::UnexpectedEnd));
+        }
+        let (value, len) = T::scan(self.rest()).map_err(|kind| self.error(kind))?;
+        // Numbers never contain newlines, so the line bookkeeping can be skipped.
+        self.pos += len;
+        Ok(value)
+    }
+
+    /// Consumes `expected` or fails without consuming anything.
+    pub fn expect(&mut self, expected: u8) -> Result<(), ParseError> {
+        match self.peek() {
+            Some(b) if b == expected => {
+                self.advance(1);
+                Ok(())
+            }
+            Some(_) => Err(self.error(ErrorKind::Expected(expected))),
+            None => Err(self.error(ErrorKind::UnexpectedEnd)),
+        }
+    }
+
+    /// Consumes `tag` if the input continues with it.
+    pub fn eat(&mut self, tag: &str) -> bool {
+        let matches = self.rest().starts_with(tag.as_bytes());
+        if matches {
+            self.advance(tag.len());
+        }
+        matches
+    }
+
+    /// Consumes bytes while `pred` holds and returns them.
+    pub fn take_while(&mut self, mut pred: impl FnMut(u8) -> bool) -> &'a [u8] {
+        let start = self.pos;
+        let len = self.rest().iter().take_while(|&&b| pred(b)).count();
+        self.advance(len);
+        &self.bytes[start..start + len]
+    }
+
+    /// Skips spaces and tabs, but not newlines.
+    pub fn skip_spaces(&mut self) {
+        self.take_while(|b| b == b' ' || b == b'\t');
+    }
+
+    /// Fails unless the whole input was consumed.
+    pub fn finish(&self) -> Result<(), ParseError> {
+        if self.is_eof() {
+            Ok(())
+        } else {
+            Err(self.error(ErrorKind::TrailingInput))
+        }
+    }
+
+    /// Runs `f`, rewinding the cursor if it fails.
+    pub fn optional<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T, ParseError>) -> Option<T> {
+        let checkpoint = self.clone();
+        f(self).inspect_err(|_| *self = checkpoint).ok()
+    }
+
+    /// Parses one or more items with `f`, separated by `delim`.
+    pub fn separated<T>(
+        &mut self,
+        delim: u8,
+        mut f: impl FnMut(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        let mut items = vec![f(self)?];
+        while self.peek() == Some(delim) {
+            self.advance(1);
+            items.push(f(self)?);
+        }
+        Ok(items)
+    }
+
+    /// Parses `a<delim>b` into a pair of integers.
+    pub fn pair<A: Integer, B: Integer>(&mut self, delim: u8) -> Result<(A, B), ParseError> {
+        let a = self.int()?;
+        self.expect(delim)?;
+        Ok((a, self.int()?))
+    }
+}
+
+/// Parses an entire string as a single integer.
+pub fn int<T: Integer>(s: &str) -> Result<T, ParseError> {
+    let mut cursor = Cursor::new(s);
+    let value = cursor.int()?;
+    cursor.finish()?;
+    Ok(value)
+}
+
+/// Parses an entire string of the form `a<delim>b`, e.g. `"328412-412772"`.
+pub fn pair<A: Integer, B: Integer>(s: &str, delim: u8) -> Result<(A, B), ParseError> {
+    let mut cursor = Cursor::new(s);
+    let value = cursor.pair(delim)?;
+    cursor.finish()?;
+    Ok(value)
+}
+
+/// Parses every line with `f`, reporting errors with their line numbers.
+/// Each line has to be consumed entirely.
+pub fn lines<'a, T>(
+    input: &'a str,
+    mut f: impl FnMut(&mut Cursor<'a>) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let mut cursor = Cursor::at_line(line, i);
+            let value = f(&mut cursor)?;
+            cursor.finish()?;
+            Ok(value)
+        })
+        .collect()
+}
+
+/// Extracts every integer in `line`, skipping any other bytes.
+/// For signed types a `-` directly in front of a digit is taken as the sign.
+pub fn ints<T: Integer>(line: &str) -> impl Iterator<Item = Result<T, ParseError>> + '_ {
+    let mut cursor = Cursor::new(line);
+    std::iter::from_fn(move || {
+        loop {
+            match cursor.rest() {
+                [] => return None,
+                [b'-', d, ..] if d.is_ascii_digit() => {
+                    if let Some(value) = cursor.optional(|c| c.int()) {
+                        return Some(Ok(value));
+                    }
+                    // Unsigned types reject the sign; parse the digits on their own.
+                    cursor.advance(1);
+                }
+                [d, ..] if d.is_ascii_digit() => {
+                    let result = cursor.int();
+                    if result.is_err() {
+                        cursor.take_while(|b| b.is_ascii_digit());
+                    }
+                    return Some(result);
+                }
+                _ => cursor.advance(1),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integers() {
+        assert_eq!(int::<u64>("18446744073709551615"), Ok(u64::MAX));
+        assert_eq!(int::<i8>("-128"), Ok(i8::MIN));
+        assert_eq!(int::<i8>("+127"), Ok(i8::MAX));
+        assert_eq!(int::<i8>("128").unwrap_err().kind, ErrorKind::Overflow);
+        assert_eq!(
+            int::<u32>("-1").unwrap_err().kind,
+            ErrorKind::ExpectedNumber
+        );
+        assert_eq!(int::<u32>("").unwrap_err().kind, ErrorKind::UnexpectedEnd);
+        assert_eq!(
+            int::<u32>("12x"),
+            Err(ParseError {
+                kind: ErrorKind::TrailingInput,
+                line: 1,
+                column: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_pairs_with_positions() {
+        assert_eq!(
+            pair::<u64, u64>("328412-412772", b'-'),
+            Ok((328412, 412772))
+        );
+        let err = lines("3-5\n10-14\n16:20", |c| c.pair::<u64, u64>(b'-')).unwrap_err();
+        assert_eq!(
+            (err.kind, err.line, err.column),
+            (ErrorKind::Expected(b'-'), 3, 3)
+        );
+        assert_eq!(err.to_string(), "3:3: expected '-'");
+    }
+
+    #[test]
+    fn test_ints() {
+        let found: Vec<i64> = ints("x=-3, y=14..-7 z").map(Result::unwrap).collect();
+        assert_eq!(found, [-3, 14, -7]);
+        let found: Vec<u8> = ints("a-1b300c2").filter_map(Result::ok).collect();
+        assert_eq!(found, [1, 2]);
+    }
+
+    #[test]
+    fn test_combinators() {
+        let mut cursor = Cursor::new("11-22,95-115\n1 2");
+        let ranges = cursor
+            .separated(b',', |c| c.pair::<u32, u32>(b'-'))
+            .unwrap();
+        assert_eq!(ranges, [(11, 22), (95, 115)]);
+        assert!(cursor.eat("\n"));
+        assert_eq!(cursor.optional(|c| c.pair::<u8, u8>(b'-')), None);
+        assert_eq!(cursor.int::<u8>(), Ok(1));
+        cursor.skip_spaces();
+        assert_eq!(cursor.int::<u8>(), Ok(2));
+        assert!(cursor.finish().is_ok());
+        assert_eq!(cursor.error(ErrorKind::UnexpectedEnd).line, 2);
+    }
+
+    #[test]
+    fn test_input_error() {
+        let input = "3-5\n10:14";
+        let err = lines(input, |c| c.pair::<u64, u64>(b'-')).unwrap_err();
+        assert_eq!(
+            InputError::new(5, input, &err).to_string(),
+            "day 05: 2:3: expected '-'\n  |\n2 | 10:14\n  |   ^"
+        );
+
+        // Long lines are cut down around the column.
+        let input = format!("{}x{}", "1".repeat(50), "2".repeat(50));
+        let at = |column| ParseError {
+            kind: ErrorKind::UnknownToken,
+            line: 1,
+            column,
+        };
+        let report = InputError::new(2, &input, &at(51));
+        assert_eq!(report.snippet.chars().nth(report.caret), Some('x'));
+        assert_eq!(report.snippet.len(), 3 + 61 + 3);
+        assert_eq!(
+            InputError::new(2, &input, &at(1)).snippet,
+            format!("{}...", "1".repeat(31))
+        );
+        let report = InputError::new(2, &input, &at(200));
+        assert_eq!((report.caret, report.snippet.len()), (33, 33));
+    }
+}
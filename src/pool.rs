@@ -0,0 +1,90 @@
+use rayon::ThreadPoolBuilder;
+use std::env;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// Sizes rayon's global pool, like the `--threads` flag: `sequential`, `auto` or a
+/// thread count. Without either, rayon starts its pool lazily on first use.
+pub const THREADS_VAR: &str = "AOC_THREADS";
+
+/// How rayon's global pool is set up before a day runs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Threads {
+    /// A pool of one thread, which is the calling thread itself, so every rayon call
+    /// executes inline and no worker thread is spawned.
+    Sequential,
+    /// As many threads as rayon would pick, i.e. `RAYON_NUM_THREADS` or one per core.
+    Auto,
+    /// A fixed number of threads.
+    Count(usize),
+}
+
+impl FromStr for Threads {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sequential" => Ok(Self::Sequential),
+            "auto" => Ok(Self::Auto),
+            _ => match s.parse() {
+                Ok(count) if count > 0 => Ok(Self::Count(count)),
+                _ => Err(format!(
+                    "threads must be `sequential`, `auto` or a positive count, not {s:?}"
+                )),
+            },
+        }
+    }
+}
+
+/// The pool requested by a `--threads` flag, which is removed from `args`, or else by
+/// [`THREADS_VAR`].
+pub fn requested(args: &mut Vec<String>) -> Result<Option<Threads>, String> {
+    if let Some(index) = args.iter().position(|arg| arg == "--threads") {
+        if index + 1 == args.len() {
+            return Err("--threads needs a value".to_string());
+        }
+        let threads = args.remove(index + 1).parse()?;
+        args.remove(index);
+        return Ok(Some(threads));
+    }
+    env::var(THREADS_VAR)
+        .ok()
+        .map(|threads| threads.parse())
+        .transpose()
+}
+
+/// Builds rayon's global pool and waits until every thread is running, returning how
+/// long that took. Must run before the pool is first used, on the thread that should
+/// join a [`Threads::Sequential`] pool.
+pub fn init(threads: Threads) -> Result<Duration, String> {
+    let start = Instant::now();
+    let builder = match threads {
+        Threads::Sequential => ThreadPoolBuilder::new().num_threads(1).use_current_thread(),
+        Threads::Auto => ThreadPoolBuilder::new(),
+        Threads::Count(count) => ThreadPoolBuilder::new().num_threads(count),
+    };
+    builder.build_global().map_err(|err| err.to_string())?;
+    if threads != Threads::Sequential {
+        // Threads are spawned asynchronously; a broadcast returns once all of them run.
+        rayon::broadcast(|_| ());
+    }
+    Ok(start.elapsed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requested() {
+        let mut args = vec!["--grid".to_string(), "bits".to_string()];
+        args.extend(["--threads".to_string(), "4".to_string()]);
+        assert_eq!(requested(&mut args), Ok(Some(Threads::Count(4))));
+        assert_eq!(args, ["--grid", "bits"]);
+
+        assert_eq!("sequential".parse(), Ok(Threads::Sequential));
+        assert_eq!("auto".parse(), Ok(Threads::Auto));
+        assert!("0".parse::<Threads>().is_err());
+        assert!(requested(&mut vec!["--threads".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,84 @@
+/// Elements picked from a sequence, in their original order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Selection<T> {
+    /// Positions of the picked elements, ascending.
+    pub indices: Vec<usize>,
+    /// The picked elements.
+    pub values: Vec<T>,
+}
+
+/// Picks `k` elements preserving their order such that the resulting sequence is
+/// lexicographically largest. Ties are broken towards the earliest indices.
+/// If `k` exceeds the number of items, all items are picked.
+///
+/// For example, the largest 2-element subsequence of `8 1 1 9` is `8 9`.
+pub fn select_max<T: Ord + Clone>(items: &[T], k: usize) -> Selection<T> {
+    select_by(items, k, |candidate, top| candidate > top)
+}
+
+/// Like [`select_max`], but picks the lexicographically smallest sequence.
+pub fn select_min<T: Ord + Clone>(items: &[T], k: usize) -> Selection<T> {
+    select_by(items, k, |candidate, top| candidate < top)
+}
+
+/// Monotonic stack: drop a previously picked element whenever a later `better` one
+/// arrives, as long as enough elements remain to still pick `k`.
+fn select_by<T: Clone>(
+    items: &[T],
+    k: usize,
+    mut better: impl FnMut(&T, &T) -> bool,
+) -> Selection<T> {
+    let target_len = k.min(items.len());
+    let mut to_remove = items.len() - target_len;
+    let mut stack: Vec<usize> = Vec::with_capacity(items.len());
+    for (i, item) in items.iter().enumerate() {
+        while to_remove > 0
+            && let Some(&top) = stack.last()
+            && better(item, &items[top])
+        {
+            stack.pop();
+            to_remove -= 1;
+        }
+        stack.push(i);
+    }
+    stack.truncate(target_len);
+
+    let values = stack.iter().map(|&i| items[i].clone()).collect();
+    Selection {
+        indices: stack,
+        values,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_max() {
+        let digits = [8, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 9];
+        let selection = select_max(&digits, 2);
+        assert_eq!(selection.values, [8, 9]);
+        assert_eq!(selection.indices, [0, 14]);
+
+        let digits = [2, 3, 4, 2, 3, 4, 2, 3, 4, 2, 3, 4, 2, 7, 8];
+        assert_eq!(
+            select_max(&digits, 12).values,
+            [4, 3, 4, 2, 3, 4, 2, 3, 4, 2, 7, 8]
+        );
+    }
+
+    #[test]
+    fn test_ties_and_short_input() {
+        assert_eq!(select_max(&[5, 5, 5], 2).indices, [0, 1]);
+        assert_eq!(select_max(&[1, 2], 5).values, [1, 2]);
+        assert_eq!(select_max::<u8>(&[], 3).indices, []);
+    }
+
+    #[test]
+    fn test_select_min() {
+        let selection = select_min(&["b", "a", "c", "a"], 2);
+        assert_eq!(selection.values, ["a", "a"]);
+        assert_eq!(selection.indices, [1, 3]);
+    }
+}
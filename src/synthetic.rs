@@ -0,0 +1,176 @@
+use crate::rng::SplitMix64;
+use std::fmt::Write;
+
+/// Generates a valid input for `day` that grows linearly with `scale`, or `None` for
+/// unknown days. The same seed always yields the same input.
+///
+/// `scale` must be at least 1. It is the number of moves (day 1), ranges (day 2), banks (day 3), grid rows
+/// and columns (day 4), ranges and IDs (day 5) or problems (day 6).
+pub fn generate(day: u32, scale: usize, seed: u64) -> Option<String> {
+    let mut rng = SplitMix64::new(seed);
+    let rng = &mut rng;
+    Some(match day {
+        1 => moves(rng, scale),
+        2 => ranges(rng, scale),
+        3 => banks(rng, scale),
+        4 => grid(rng, scale),
+        5 => inventory(rng, scale),
+        6 => worksheet(rng, scale),
+        _ => return None,
+    })
+}
+
+/// Dial moves such as `L68`, one per line.
+fn moves(rng: &mut SplitMix64, count: usize) -> String {
+    let lines: Vec<String> = (0..count)
+        .map(|_| {
+            let direction = if rng.chance(0.5) { 'L' } else { 'R' };
+            format!("{direction}{}", 1 + rng.below(999))
+        })
+        .collect();
+    lines.join("\n")
+}
+
+/// Comma-separated ID ranges of 1 to 10 digits.
+fn ranges(rng: &mut SplitMix64, count: usize) -> String {
+    let ranges: Vec<String> = (0..count)
+        .map(|_| {
+            let digits = 1 + rng.below(10) as u32;
+            let start = 1 + rng.below(10u64.pow(digits));
+            format!("{start}-{}", start + rng.below(100_000))
+        })
+        .collect();
+    ranges.join(",")
+}
+
+/// Banks of 100 digits from 1 to 9, one per line.
+fn banks(rng: &mut SplitMix64, count: usize) -> String {
+    let lines: Vec<String> = (0..count)
+        .map(|_| {
+            (0..100)
+                .map(|_| char::from(b'1' + rng.below(9) as u8))
+                .collect()
+        })
+        .collect();
+    lines.join("\n")
+}
+
+/// A square grid of rolls (`@`) and empty cells (`.`).
+fn grid(rng: &mut SplitMix64, size: usize) -> String {
+    let lines: Vec<String> = (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| if rng.chance(0.65) { '@' } else { '.' })
+                .collect()
+        })
+        .collect();
+    lines.join("\n")
+}
+
+/// Overlapping fresh ranges, a blank line and ingredient IDs, starting below 10^15.
+fn inventory(rng: &mut SplitMix64, count: usize) -> String {
+    const SPACE: u64 = 1_000_000_000_000_000;
+    let mut out = String::new();
+    for _ in 0..count {
+        let start = rng.below(SPACE);
+        writeln!(out, "{start}-{}", start + rng.below(SPACE / count as u64)).unwrap();
+    }
+    for _ in 0..count {
+        write!(out, "\n{}", rng.below(SPACE)).unwrap();
+    }
+    out
+}
+
+/// Three rows of 1 to 3 digit numbers and an operator row, one problem per column group.
+/// Numbers stay small enough that even a million problems fit into `i64`.
+fn worksheet(rng: &mut SplitMix64, count: usize) -> String {
+    const HEIGHT: usize = 3;
+    let mut rows = vec![String::new(); HEIGHT + 1];
+    for problem in 0..count {
+        let width = 1 + rng.below(3) as usize;
+        let right_aligned = rng.chance(0.5);
+        for (index, row) in rows[..HEIGHT].iter_mut().enumerate() {
+            if problem > 0 {
+                row.push(' ');
+            }
+            // The first number has the full width, so no column of the group is blank.
+            let digits = if index == 0 {
+                width
+            } else {
+                1 + rng.below(width as u64) as usize
+            } as u32;
+            let low = 10u64.pow(digits - 1);
+            let number = low + rng.below(9 * low);
+            if right_aligned {
+                write!(row, "{number:>width$}").unwrap();
+            } else {
+                write!(row, "{number:<width$}").unwrap();
+            }
+        }
+        let operator = if rng.chance(0.5) { '+' } else { '*' };
+        if problem > 0 {
+            rows[HEIGHT].push(' ');
+        }
+        write!(rows[HEIGHT], "{operator:<width$}").unwrap();
+    }
+    rows.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day04::Representation;
+    use crate::erosion::Init;
+    use crate::{day01, day02, day03, day04, day05, day06};
+    use num_bigint::BigUint;
+
+    #[test]
+    fn test_deterministic() {
+        for day in 1..=6 {
+            assert_eq!(generate(day, 50, 7), generate(day, 50, 7));
+            assert_ne!(generate(day, 50, 7), generate(day, 50, 8));
+        }
+        assert_eq!(generate(7, 50, 7), None);
+    }
+
+    #[test]
+    fn test_scale() {
+        assert_eq!(generate(1, 30, 1).unwrap().lines().count(), 30);
+        assert_eq!(generate(2, 30, 1).unwrap().split(',').count(), 30);
+        let grid = generate(4, 30, 1).unwrap();
+        assert!(grid.lines().all(|line| line.len() == 30));
+        assert_eq!(generate(5, 30, 1).unwrap().lines().count(), 61);
+        let worksheet = generate(6, 30, 1).unwrap();
+        let operators = worksheet.lines().last().unwrap();
+        assert_eq!(operators.split_whitespace().count(), 30);
+    }
+
+    #[test]
+    fn test_days_solve_generated_inputs() {
+        // Each check fails unless the day accepts the input; days with more than one
+        // solver also compare them.
+        type Check = fn(&str);
+        let checks: [(u32, usize, Check); 6] = [
+            (1, 500, |input| assert!(day01::solve(input).is_ok())),
+            (2, 500, |input| assert!(day02::solve(input).is_ok())),
+            (3, 50, |input| assert!(day03::solve(input).is_ok())),
+            (4, 100, |input| {
+                let bytes = day04::solve_with(input, Representation::Bytes, Init::Sequential);
+                assert!(bytes.is_ok());
+                assert_eq!(
+                    day04::solve_with(input, Representation::Bits, Init::Sequential),
+                    bytes
+                );
+            }),
+            (5, 500, |input| assert!(day05::solve(input).is_ok())),
+            (6, 500, |input| {
+                let (one, two) = day06::solve::<u64>(input).unwrap();
+                let big = day06::solve::<BigUint>(input);
+                assert_eq!(big, Ok((one.into(), two.into())));
+            }),
+        ];
+        for (day, scale, check) in checks {
+            check(&generate(day, scale, day.into()).unwrap());
+        }
+    }
+}
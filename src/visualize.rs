@@ -0,0 +1,196 @@
+use crate::erosion::Timeline;
+use crate::grid::Grid;
+use std::io::{self, Write};
+
+pub type Rgb = [u8; 3];
+
+/// An RGB image, one color per pixel in row-major order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<Rgb>,
+}
+
+impl Frame {
+    /// Renders the inner cells of `grid` as `scale` x `scale` pixel blocks colored by `color`.
+    pub fn from_grid<T>(grid: &Grid<T>, scale: usize, mut color: impl FnMut(&T) -> Rgb) -> Self {
+        let (width, height) = (grid.cols() * scale, grid.rows() * scale);
+        let mut pixels = Vec::with_capacity(width * height);
+        for row in grid.iter_rows() {
+            let line: Vec<Rgb> = row
+                .iter()
+                .flat_map(|cell| std::iter::repeat_n(color(cell), scale))
+                .collect();
+            for _ in 0..scale {
+                pixels.extend_from_slice(&line);
+            }
+        }
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Writes the frame as a binary PPM (P6) image.
+    pub fn write_ppm(&self, mut w: impl Write) -> io::Result<()> {
+        write!(w, "P6\n{} {}\n255\n", self.width, self.height)?;
+        w.write_all(self.pixels.as_flattened())
+    }
+
+    /// Writes the frame as an 8-bit RGB PNG image.
+    #[cfg(feature = "visualize")]
+    pub fn write_png(&self, w: impl Write) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(w, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(self.pixels.as_flattened())?;
+        Ok(writer.finish()?)
+    }
+}
+
+/// Writes `frames` as an endlessly looping GIF animation showing each frame for
+/// `delay` hundredths of a second. All frames must have the same size and
+/// together use at most 256 distinct colors.
+#[cfg(feature = "visualize")]
+pub fn write_gif(frames: &[Frame], delay: u16, w: impl Write) -> io::Result<()> {
+    let Some(first) = frames.first() else {
+        return Err(io::Error::other("no frames to write"));
+    };
+    let too_large = || io::Error::other("frame too large for a GIF");
+    let width = u16::try_from(first.width).map_err(|_| too_large())?;
+    let height = u16::try_from(first.height).map_err(|_| too_large())?;
+
+    let mut palette: Vec<Rgb> = Vec::new();
+    let mut indexed = Vec::with_capacity(frames.len());
+    for frame in frames {
+        if (frame.width, frame.height) != (first.width, first.height) {
+            return Err(io::Error::other("frames differ in size"));
+        }
+        let mut indices = Vec::with_capacity(frame.pixels.len());
+        for pixel in &frame.pixels {
+            let index = match palette.iter().position(|c| c == pixel) {
+                Some(index) => index,
+                None if palette.len() < 256 => {
+                    palette.push(*pixel);
+                    palette.len() - 1
+                }
+                None => return Err(io::Error::other("more than 256 colors")),
+            };
+            indices.push(index as u8);
+        }
+        indexed.push(indices);
+    }
+
+    let mut encoder =
+        gif::Encoder::new(w, width, height, palette.as_flattened()).map_err(io::Error::other)?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(io::Error::other)?;
+    for indices in indexed {
+        let mut frame = gif::Frame::from_indexed_pixels(width, height, indices, None);
+        frame.delay = delay;
+        encoder.write_frame(&frame).map_err(io::Error::other)?;
+    }
+    Ok(())
+}
+
+/// Colors of the cell states of an erosion.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Palette {
+    /// Cells that were never active.
+    pub empty: Rgb,
+    /// Cells that are still active.
+    pub active: Rgb,
+    /// Cells removed in the generation shown by the frame.
+    pub removing: Rgb,
+    /// Cells removed in an earlier generation.
+    pub removed: Rgb,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self {
+            empty: [16, 16, 24],
+            active: [230, 230, 230],
+            removing: [220, 60, 60],
+            removed: [70, 70, 90],
+        }
+    }
+}
+
+/// One frame for the initial grid plus one per generation, highlighting the cells
+/// removed in that generation.
+pub fn timeline_frames(timeline: &Timeline, palette: &Palette, scale: usize) -> Vec<Frame> {
+    let survivors = &timeline.survivors;
+    let mut state = Grid::filled(
+        survivors.rows(),
+        survivors.cols(),
+        (false, None),
+        (false, None),
+    );
+    for index in survivors.indices() {
+        state[index] = (survivors[index], timeline.generations[index]);
+    }
+
+    (0..=timeline.generation_count())
+        .map(|shown| {
+            Frame::from_grid(&state, scale, |&(survives, generation)| match generation {
+                _ if survives => palette.active,
+                Some(g) if g == shown => palette.removing,
+                Some(g) if g < shown => palette.removed,
+                Some(_) => palette.active,
+                None => palette.empty,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::erosion::Erosion;
+    use crate::grid::Neighborhood;
+
+    #[test]
+    fn test_ppm() {
+        let grid = Grid::from_ascii("@.\n.@", b'.');
+        let frame = Frame::from_grid(&grid, 2, |&c| if c == b'@' { [255, 0, 0] } else { [0; 3] });
+        assert_eq!((frame.width, frame.height), (4, 4));
+
+        let mut out = Vec::new();
+        frame.write_ppm(&mut out).unwrap();
+        assert!(out.starts_with(b"P6\n4 4\n255\n"));
+        assert_eq!(out.len(), 11 + 4 * 4 * 3);
+        // Second pixel row, second pixel: still the scaled-up top-left cell.
+        assert_eq!(&out[11 + 4 * 3 + 3..][..3], [255, 0, 0]);
+    }
+
+    #[test]
+    fn test_timeline_frames() {
+        let grid = Grid::from_ascii("@@@\n@.@\n@@@", b'.');
+        let timeline = Erosion::new(3, Neighborhood::Moore).timeline(&grid, |&c| c == b'@');
+        let frames = timeline_frames(&timeline, &Palette::default(), 3);
+        assert_eq!(frames.len(), timeline.generation_count() as usize + 1);
+        assert_eq!((frames[0].width, frames[0].height), (9, 9));
+    }
+
+    #[cfg(feature = "visualize")]
+    #[test]
+    fn test_png_and_gif_signatures() {
+        let grid = Grid::from_ascii("@@@\n@.@\n@@@", b'.');
+        let timeline = Erosion::new(3, Neighborhood::Moore).timeline(&grid, |&c| c == b'@');
+        let frames = timeline_frames(&timeline, &Palette::default(), 3);
+
+        let mut png = Vec::new();
+        frames[0].write_png(&mut png).unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+
+        let mut gif = Vec::new();
+        write_gif(&frames, 20, &mut gif).unwrap();
+        assert!(gif.starts_with(b"GIF89a"));
+        assert_eq!(gif.last(), Some(&0x3b));
+    }
+}